@@ -3,6 +3,7 @@
 import React, { useState, useCallback } from "react";
 import { open } from "@tauri-apps/api/dialog";
 import { readTextFile } from "@tauri-apps/api/fs";
+import { invoke } from "@tauri-apps/api/tauri";
 import {
 	HiOutlineDocumentText,
 	HiOutlineCog,
@@ -65,7 +66,10 @@ function AppControls() {
 
 				const contents = await readTextFile(selected);
 				const importedConfig = JSON.parse(contents);
-				importConfig(importedConfig);
+				const normalized = await invoke("normalize_blueprints_command", {
+					config: importedConfig,
+				});
+				importConfig(normalized.config);
 
 				await new Promise((resolve) => setTimeout(resolve, 2000 - 20 * 100));
 
@@ -79,6 +83,29 @@ function AppControls() {
 					draggable: true,
 					theme: "dark",
 				});
+				if (normalized.issues.length > 0) {
+					const shown = normalized.issues
+						.slice(0, 5)
+						.map((issue) => `${issue.pointer}: ${issue.message}`);
+					if (normalized.issues.length > shown.length) {
+						shown.push(`...and ${normalized.issues.length - shown.length} more`);
+					}
+					toast.warn(
+						`${normalized.issues.length} blueprint${
+							normalized.issues.length > 1 ? "s" : ""
+						} couldn't be normalized:\n${shown.join("\n")}`,
+						{
+							position: "bottom-right",
+							autoClose: 10000,
+							hideProgressBar: false,
+							closeOnClick: true,
+							pauseOnHover: true,
+							draggable: true,
+							theme: "dark",
+							style: { whiteSpace: "pre-line" },
+						}
+					);
+				}
 			}
 		} catch (error) {
 			console.error("Error importing file:", error);
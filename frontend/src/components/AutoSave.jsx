@@ -43,7 +43,7 @@ function AutoSave() {
 			});
 		} catch (error) {
 			console.error("Auto-save failed:", error);
-			toast.error("Failed to auto-save configuration", {
+			toast.error("Failed to auto-save configuration: " + error.toString(), {
 				position: "bottom-right",
 				autoClose: 3000,
 				hideProgressBar: false,
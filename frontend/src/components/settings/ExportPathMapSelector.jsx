@@ -0,0 +1,83 @@
+// src/components/settings/ExportPathMapSelector.jsx
+
+import React, { useState, useEffect } from "react";
+import { invoke } from "@tauri-apps/api/tauri";
+import { toast } from "react-toastify";
+
+// The map a single export path's server runs, and whether beacons it lacks are left out on export
+const ExportPathMapSelector = ({ path }) => {
+	const [maps, setMaps] = useState([]);
+	const [map, setMap] = useState("");
+	const [dropIncompatible, setDropIncompatible] = useState(false);
+
+	useEffect(() => {
+		if (!path) return;
+		const load = async () => {
+			try {
+				const [catalogMaps, pathMap] = await Promise.all([
+					invoke("list_catalog_maps_command"),
+					invoke("get_export_path_map_command", { path }),
+				]);
+				setMaps(catalogMaps);
+				setMap(pathMap ? pathMap.map : "");
+				setDropIncompatible(pathMap ? pathMap.drop_incompatible : false);
+			} catch (error) {
+				console.error("Failed to load export path map:", error);
+			}
+		};
+		load();
+	}, [path]);
+
+	const save = async (newMap, newDropIncompatible) => {
+		try {
+			await invoke("set_export_path_map_command", {
+				path,
+				map: newMap || null,
+				dropIncompatible: newMap ? newDropIncompatible : false,
+			});
+			setMap(newMap);
+			setDropIncompatible(newMap ? newDropIncompatible : false);
+		} catch (error) {
+			console.error("Failed to save export path map:", error);
+			toast.error("Failed to save export path map: " + error.toString(), {
+				position: "bottom-right",
+				autoClose: 3000,
+				hideProgressBar: false,
+				closeOnClick: true,
+				pauseOnHover: true,
+				draggable: true,
+				theme: "dark",
+			});
+		}
+	};
+
+	if (!path) return null;
+
+	return (
+		<div className="flex items-center space-x-4 text-sm text-gray-300">
+			<select
+				value={map}
+				onChange={(e) => save(e.target.value, dropIncompatible)}
+				className="px-2 py-1 text-white bg-dark-black rounded border border-gray-600 focus:ring-blue-500 focus:border-blue-500">
+				<option value="">Any map</option>
+				{maps.map((name) => (
+					<option key={name} value={name}>
+						{name}
+					</option>
+				))}
+			</select>
+			<label className={`flex items-center ${!map && "opacity-50"}`}>
+				<input
+					type="checkbox"
+					checked={dropIncompatible}
+					disabled={!map}
+					onChange={(e) => save(map, e.target.checked)}
+					className="mr-2"
+				/>
+				Leave out beacons that don't exist on this map
+			</label>
+		</div>
+	);
+};
+
+export default ExportPathMapSelector;
@@ -0,0 +1,173 @@
+// src/components/settings/SecretsSettings.jsx
+
+import React, { useState, useEffect, useCallback } from "react";
+import { invoke } from "@tauri-apps/api/tauri";
+import { toast } from "react-toastify";
+import { useConfig } from "../ConfigContext";
+
+const LOCKED_PLACEHOLDER = "<locked>";
+
+const toastOptions = {
+	position: "bottom-right",
+	autoClose: 3000,
+	hideProgressBar: false,
+	closeOnClick: true,
+	pauseOnHover: true,
+	draggable: true,
+	theme: "dark",
+};
+
+// Where the key for MysqlPass and the Discord URL lives, unlocking it and switching to another one
+const SecretsSettings = () => {
+	const { currentlyLoadedConfig, updateConfig } = useConfig();
+	const [status, setStatus] = useState(null);
+	const [passphrase, setPassphrase] = useState("");
+	const [newSource, setNewSource] = useState("keyring");
+	const [newPassphrase, setNewPassphrase] = useState("");
+
+	const loadStatus = useCallback(async () => {
+		try {
+			setStatus(await invoke("get_secrets_status_command"));
+		} catch (error) {
+			console.error("Failed to load secrets status:", error);
+		}
+	}, []);
+
+	useEffect(() => {
+		loadStatus();
+	}, [loadStatus]);
+
+	// Saved configs reach the editor with placeholders, swap in the real values on request
+	const revealLoadedSecrets = async () => {
+		if (!currentlyLoadedConfig) return;
+		const stored = await invoke("reveal_config_secrets_command", {
+			configId: currentlyLoadedConfig.id,
+		});
+		updateConfig((prev) => {
+			const next = { ...prev };
+			if (prev.Mysql?.MysqlPass === LOCKED_PLACEHOLDER) {
+				next.Mysql = { ...prev.Mysql, MysqlPass: stored.Mysql?.MysqlPass ?? "" };
+			}
+			if (prev.General?.Discord?.URL === LOCKED_PLACEHOLDER) {
+				next.General = {
+					...prev.General,
+					Discord: { ...prev.General.Discord, URL: stored.General?.Discord?.URL ?? "" },
+				};
+			}
+			return next;
+		});
+	};
+
+	const handleUnlock = async () => {
+		try {
+			await invoke("unlock_secrets_command", { passphrase });
+			setPassphrase("");
+			await loadStatus();
+			toast.success("Secrets unlocked", toastOptions);
+		} catch (error) {
+			console.error("Failed to unlock secrets:", error);
+			toast.error("Failed to unlock secrets: " + error.toString(), toastOptions);
+		}
+	};
+
+	const handleReveal = async () => {
+		try {
+			await revealLoadedSecrets();
+		} catch (error) {
+			console.error("Failed to reveal secrets:", error);
+			toast.error("Failed to reveal secrets: " + error.toString(), toastOptions);
+		}
+	};
+
+	const handleRotate = async () => {
+		try {
+			await invoke("rotate_secrets_key_command", {
+				source: newSource,
+				passphrase: newSource === "passphrase" ? newPassphrase : null,
+			});
+			setNewPassphrase("");
+			await loadStatus();
+			toast.success("Secrets re-encrypted with the new key", toastOptions);
+		} catch (error) {
+			console.error("Failed to change secrets key:", error);
+			toast.error("Failed to change secrets key: " + error.toString(), toastOptions);
+		}
+	};
+
+	if (!status) return null;
+
+	const locked = status.source === "passphrase" && !status.unlocked;
+
+	return (
+		<div className="space-y-4 text-sm">
+			<p className="text-gray-400">
+				MySQL password and Discord webhook are encrypted with{" "}
+				{status.source === "passphrase"
+					? "a passphrase"
+					: status.source === "keyring"
+					? "a key in the OS keyring"
+					: "a key that will be put in the OS keyring on the next save"}
+				{locked ? " and are locked." : "."}
+			</p>
+			{status.keyring_error && (
+				<p className="text-yellow-400">
+					The OS keyring can't be used ({status.keyring_error}). Configs with
+					secrets can't be saved until it is available or a passphrase is set.
+				</p>
+			)}
+
+			{locked && (
+				<div className="flex items-center space-x-2">
+					<input
+						type="password"
+						value={passphrase}
+						onChange={(e) => setPassphrase(e.target.value)}
+						onKeyDown={(e) => e.key === "Enter" && handleUnlock()}
+						placeholder="Passphrase"
+						className="flex-1 px-2 py-1 text-white bg-dark-black rounded border border-gray-600 focus:ring-blue-500 focus:border-blue-500"
+					/>
+					<button
+						onClick={handleUnlock}
+						className="px-3 py-1 bg-blue-600 text-white rounded hover:bg-blue-700 transition-colors">
+						Unlock
+					</button>
+				</div>
+			)}
+
+			{currentlyLoadedConfig && !locked && (
+				<button
+					onClick={handleReveal}
+					className="px-3 py-1 bg-gray-700 text-white rounded hover:bg-gray-600 transition-colors">
+					Reveal secrets in the editor
+				</button>
+			)}
+
+			<div className="flex items-center space-x-2">
+				<select
+					value={newSource}
+					onChange={(e) => setNewSource(e.target.value)}
+					className="px-2 py-1 text-white bg-dark-black rounded border border-gray-600 focus:ring-blue-500 focus:border-blue-500">
+					<option value="keyring">New OS keyring key</option>
+					<option value="passphrase">New passphrase</option>
+				</select>
+				{newSource === "passphrase" && (
+					<input
+						type="password"
+						value={newPassphrase}
+						onChange={(e) => setNewPassphrase(e.target.value)}
+						placeholder="New passphrase"
+						className="flex-1 px-2 py-1 text-white bg-dark-black rounded border border-gray-600 focus:ring-blue-500 focus:border-blue-500"
+					/>
+				)}
+				<button
+					onClick={handleRotate}
+					disabled={locked || (newSource === "passphrase" && !newPassphrase)}
+					className="px-3 py-1 bg-blue-600 text-white rounded hover:bg-blue-700 transition-colors disabled:opacity-50">
+					Change key
+				</button>
+			</div>
+		</div>
+	);
+};
+
+export default SecretsSettings;
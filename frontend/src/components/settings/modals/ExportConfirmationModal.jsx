@@ -13,6 +13,8 @@ function ExportConfirmationModal({ isOpen, onClose }) {
 	const [exportedFilePaths, setExportedFilePaths] = useState([]);
 	const [progress, setProgress] = useState(0);
 	const [currentExportPaths, setCurrentExportPaths] = useState([]);
+	const [deniedCommands, setDeniedCommands] = useState([]);
+	const [acknowledgeCommands, setAcknowledgeCommands] = useState(false);
 	const { config, updateConfig, currentlyLoadedConfig } = useConfig();
 
 	const loadExportPaths = useCallback(async () => {
@@ -43,11 +45,24 @@ function ExportConfirmationModal({ isOpen, onClose }) {
 		}
 	}, [currentlyLoadedConfig]);
 
+	const loadDeniedCommands = useCallback(async () => {
+		try {
+			const issues = await invoke("analyze_commands_command", { config });
+			setDeniedCommands(
+				issues.filter((issue) => issue.problem === "denied_command")
+			);
+			setAcknowledgeCommands(false);
+		} catch (error) {
+			console.error("Failed to analyze commands:", error);
+		}
+	}, [config]);
+
 	useEffect(() => {
 		if (isOpen) {
 			loadExportPaths();
+			loadDeniedCommands();
 		}
-	}, [isOpen, loadExportPaths]);
+	}, [isOpen, loadExportPaths, loadDeniedCommands]);
 
 	const handleExport = useCallback(async () => {
 		if (currentExportPaths.length === 0) {
@@ -86,6 +101,8 @@ function ExportConfirmationModal({ isOpen, onClose }) {
 			const results = await invoke("export_config", {
 				config: config,
 				exportPaths: currentExportPaths,
+				configId: currentlyLoadedConfig?.id ?? null,
+				acknowledgeCommands,
 			});
 			console.log("Export results:", results);
 			setExportedFilePaths(results.map((r) => r.file_path));
@@ -110,7 +127,12 @@ function ExportConfirmationModal({ isOpen, onClose }) {
 				theme: "dark",
 			});
 		}
-	}, [config, updateConfig, currentExportPaths]);
+	}, [
+		config,
+		updateConfig,
+		currentExportPaths,
+		acknowledgeCommands,
+	]);
 
 	const handleClose = () => {
 		setModalState("confirmation");
@@ -124,6 +146,8 @@ function ExportConfirmationModal({ isOpen, onClose }) {
 				await invoke("force_export_config", {
 					config: config,
 					filePath: path,
+					configId: currentlyLoadedConfig?.id ?? null,
+					acknowledgeCommands,
 				});
 			}
 			setModalState("success");
@@ -172,6 +196,29 @@ function ExportConfirmationModal({ isOpen, onClose }) {
 											</span>
 										))}
 									</p>
+									{deniedCommands.length > 0 && (
+										<div className="mb-4 text-red-400">
+											<p className="mb-2">
+												These command entries run commands on the deny list:
+											</p>
+											{deniedCommands.map((issue, index) => (
+												<span key={index} className="block ml-2 font-semibold">
+													{issue.key}: {issue.command}
+												</span>
+											))}
+											<label className="flex items-center mt-2 text-gray-300">
+												<input
+													type="checkbox"
+													checked={acknowledgeCommands}
+													onChange={(e) =>
+														setAcknowledgeCommands(e.target.checked)
+													}
+													className="mr-2"
+												/>
+												I understand what these commands do
+											</label>
+										</div>
+									)}
 									<div className="flex justify-end space-x-4">
 										<button
 											onClick={handleClose}
@@ -180,7 +227,8 @@ function ExportConfirmationModal({ isOpen, onClose }) {
 										</button>
 										<button
 											onClick={handleExport}
-											className="px-4 py-2 bg-blue-600 text-white rounded hover:bg-blue-700 transition-colors">
+											disabled={deniedCommands.length > 0 && !acknowledgeCommands}
+											className="px-4 py-2 bg-blue-600 text-white rounded hover:bg-blue-700 transition-colors disabled:opacity-50">
 											Export
 										</button>
 									</div>
@@ -18,6 +18,7 @@ import {
 } from "@heroicons/react/24/solid";
 import { CiFloppyDisk } from "react-icons/ci";
 import { Tooltip } from "react-tooltip";
+import ExportPathMapSelector from "../ExportPathMapSelector";
 
 function SavedConfigsModal({ isOpen, onClose }) {
 	const [savedConfigs, setSavedConfigs] = useState([]);
@@ -107,6 +108,7 @@ function SavedConfigsModal({ isOpen, onClose }) {
 				name: newConfigName,
 				config: config,
 				customExportPaths: [],
+				sourceId: currentlyLoadedConfig?.id ?? null,
 			});
 			toast.success("Config saved successfully", {
 				position: "bottom-right",
@@ -214,7 +216,7 @@ function SavedConfigsModal({ isOpen, onClose }) {
 				await loadSavedConfigs();
 			} catch (error) {
 				console.error("Failed to update config:", error);
-				toast.error("Failed to update config", {
+				toast.error("Failed to update config: " + error.toString(), {
 					position: "bottom-right",
 					autoClose: 3000,
 					hideProgressBar: false,
@@ -565,34 +567,35 @@ function SavedConfigsModal({ isOpen, onClose }) {
 											</h6>
 											{(customExportPaths[savedConfig.id] || []).map(
 												(path, index) => (
-													<div
-														key={index}
-														className="flex items-center space-x-2">
-														<input
-															type="text"
-															value={path}
-															readOnly
-															className="flex-grow px-3 py-2 text-sm text-white bg-dark-black rounded border border-gray-600 focus:ring-blue-500 focus:border-blue-500"
-														/>
-														<button
-															onClick={() => {
-																const updatedPaths = customExportPaths[
-																	savedConfig.id
-																].filter((_, i) => i !== index);
-																setCustomExportPaths({
-																	...customExportPaths,
-																	[savedConfig.id]: updatedPaths,
-																});
-																handleCustomExportPathChange(
-																	savedConfig.id,
-																	updatedPaths
-																);
-															}}
-															className="text-red-500 hover:text-red-400"
-															data-tooltip-id={`remove-export-path-${savedConfig.id}-${index}`}
-															data-tooltip-content="Remove this export path">
-															<XMarkIcon className="h-5 w-5" />
-														</button>
+													<div key={index} className="space-y-1">
+														<div className="flex items-center space-x-2">
+															<input
+																type="text"
+																value={path}
+																readOnly
+																className="flex-grow px-3 py-2 text-sm text-white bg-dark-black rounded border border-gray-600 focus:ring-blue-500 focus:border-blue-500"
+															/>
+															<button
+																onClick={() => {
+																	const updatedPaths = customExportPaths[
+																		savedConfig.id
+																	].filter((_, i) => i !== index);
+																	setCustomExportPaths({
+																		...customExportPaths,
+																		[savedConfig.id]: updatedPaths,
+																	});
+																	handleCustomExportPathChange(
+																		savedConfig.id,
+																		updatedPaths
+																	);
+																}}
+																className="text-red-500 hover:text-red-400"
+																data-tooltip-id={`remove-export-path-${savedConfig.id}-${index}`}
+																data-tooltip-content="Remove this export path">
+																<XMarkIcon className="h-5 w-5" />
+															</button>
+														</div>
+														<ExportPathMapSelector path={path} />
 													</div>
 												)
 											)}
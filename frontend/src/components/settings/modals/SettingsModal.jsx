@@ -9,6 +9,8 @@ import { toast } from "react-toastify";
 import "react-toastify/dist/ReactToastify.css";
 import { useConfig } from "../../ConfigContext";
 import { Tooltip } from "react-tooltip";
+import ExportPathMapSelector from "../ExportPathMapSelector";
+import SecretsSettings from "../SecretsSettings";
 
 // Custom Toggle component
 const Toggle = ({ checked, onChange }) => (
@@ -180,6 +182,9 @@ function SettingsModal({ isOpen, onClose }) {
 							Select
 						</button>
 					</div>
+					<div className="mt-2">
+						<ExportPathMapSelector path={outputPath} />
+					</div>
 				</div>
 
 				{/* Auto-save Settings */}
@@ -248,6 +253,12 @@ function SettingsModal({ isOpen, onClose }) {
 					</div>
 				</div>
 
+				{/* Secrets */}
+				<div className="mb-6 bg-light-black p-4 rounded-lg">
+					<h3 className="text-lg font-semibold mb-2 text-white">Secrets</h3>
+					<SecretsSettings />
+				</div>
+
 				<div className="flex justify-end">
 					<button
 						onClick={handleSave}
@@ -1,25 +1,344 @@
 // src-tauri/src/ark_data.rs
 
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
-use serde_json::Value;
+use std::path::{Path, PathBuf};
+use serde::Serialize;
+use serde_json::{Map, Value};
 use tauri::AppHandle;
 
-pub fn read_ark_data(app: AppHandle) -> Result<Value, String> {
-    let resource_path = app
-        .path_resolver()
+// Catalog sections and the fields every entry in them must carry
+pub const CATALOG_SCHEMA: [(&str, &[&str]); 4] = [
+    ("Dinos", &["ID", "Type", "Name", "EntityID", "Blueprint"]),
+    ("Items", &["ID", "Type", "Name", "ClassName", "Blueprint"]),
+    ("Engrams", &["ID", "Type", "Name", "Blueprint"]),
+    ("Beacons", &["ID", "Type", "Name", "ClassName"]),
+];
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueSeverity {
+    Error,
+    Warning,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct CatalogIssue {
+    pub severity: IssueSeverity,
+    pub category: Option<String>,
+    pub key: Option<String>,
+    pub message: String,
+}
+
+#[derive(Serialize, Debug, Default)]
+pub struct CatalogLoadReport {
+    pub source: String,
+    pub version: Option<String>,
+    pub loaded: BTreeMap<String, usize>,
+    pub skipped: usize,
+    pub issues: Vec<CatalogIssue>,
+}
+
+impl CatalogLoadReport {
+    fn error(&mut self, category: Option<&str>, key: Option<&str>, message: String) {
+        self.push(IssueSeverity::Error, category, key, message);
+    }
+
+    fn warning(&mut self, category: Option<&str>, key: Option<&str>, message: String) {
+        self.push(IssueSeverity::Warning, category, key, message);
+    }
+
+    fn push(&mut self, severity: IssueSeverity, category: Option<&str>, key: Option<&str>, message: String) {
+        self.issues.push(CatalogIssue {
+            severity,
+            category: category.map(str::to_string),
+            key: key.map(str::to_string),
+            message,
+        });
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.issues.iter().any(|issue| issue.severity == IssueSeverity::Error)
+    }
+}
+
+pub struct CatalogLoad {
+    pub data: Value,
+    pub report: CatalogLoadReport,
+}
+
+impl CatalogLoad {
+    // A catalog that failed to read or parse comes back with every section empty
+    pub fn is_usable(&self) -> bool {
+        self.report.loaded.values().any(|count| *count > 0)
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct CatalogSource {
+    pub path: String,
+    pub version: Option<String>,
+    pub bundled: bool,
+    pub usable: bool,
+    pub active: bool,
+}
+
+fn empty_catalog() -> Value {
+    let mut catalog = Map::new();
+    for (category, _) in CATALOG_SCHEMA.iter() {
+        catalog.insert(category.to_string(), Value::Object(Map::new()));
+    }
+    Value::Object(catalog)
+}
+
+pub fn load_ark_data(path: &Path) -> CatalogLoad {
+    let mut report = CatalogLoadReport {
+        source: path.to_string_lossy().into_owned(),
+        ..Default::default()
+    };
+
+    let raw = match fs::read_to_string(path) {
+        Ok(raw) => raw,
+        Err(e) => {
+            report.error(None, None, format!("Error reading file: {}", e));
+            return CatalogLoad { data: empty_catalog(), report };
+        }
+    };
+
+    let parsed: Value = match serde_json::from_str(&raw) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            report.error(None, None, format!("Error parsing JSON: {}", e));
+            return CatalogLoad { data: empty_catalog(), report };
+        }
+    };
+
+    let data = validate_ark_data(parsed, &mut report);
+    CatalogLoad { data, report }
+}
+
+pub fn validate_ark_data(parsed: Value, report: &mut CatalogLoadReport) -> Value {
+    let mut root = match parsed {
+        Value::Object(root) => root,
+        _ => {
+            report.error(None, None, "Catalog root must be a JSON object".to_string());
+            return empty_catalog();
+        }
+    };
+
+    let mut catalog = Map::new();
+    match root.remove("Version") {
+        Some(Value::String(version)) if !version.trim().is_empty() => {
+            report.version = Some(version.trim().to_string());
+            catalog.insert("Version".to_string(), Value::String(version.trim().to_string()));
+        }
+        Some(_) => report.warning(None, None, "Field \"Version\" must be a non-empty string".to_string()),
+        None => report.warning(None, None, "Catalog has no \"Version\"".to_string()),
+    }
+
+    let mut blueprint_owners: HashMap<String, (String, String)> = HashMap::new();
+
+    for (category, required) in CATALOG_SCHEMA.iter() {
+        let entries = match root.remove(*category) {
+            Some(Value::Object(entries)) => entries,
+            Some(_) => {
+                report.error(Some(category), None, format!("Section \"{}\" must be an object", category));
+                Map::new()
+            }
+            None => {
+                report.warning(Some(category), None, format!("Section \"{}\" is missing", category));
+                Map::new()
+            }
+        };
+
+        let mut valid = Map::new();
+        let mut seen_ids: HashMap<i64, String> = HashMap::new();
+        let mut seen_names: HashMap<String, String> = HashMap::new();
+
+        for (key, entry) in entries {
+            let problems = entry_schema_problems(&entry, required);
+            if !problems.is_empty() {
+                report.skipped += 1;
+                for problem in problems {
+                    report.error(Some(category), Some(&key), problem);
+                }
+                continue;
+            }
+
+            for field in required.iter().filter(|field| **field != "ID" && **field != "Name") {
+                if entry.get(*field).and_then(Value::as_str).map_or(false, |s| s.trim().is_empty()) {
+                    report.warning(Some(category), Some(&key), format!("Field \"{}\" is empty", field));
+                }
+            }
+
+            if let Some(id) = entry.get("ID").and_then(Value::as_i64) {
+                if let Some(other) = seen_ids.insert(id, key.clone()) {
+                    report.warning(Some(category), Some(&key), format!("Duplicate ID {} (also used by \"{}\")", id, other));
+                }
+            }
+
+            if let Some(name) = entry.get("Name").and_then(Value::as_str) {
+                if let Some(other) = seen_names.insert(name.to_lowercase(), key.clone()) {
+                    report.warning(Some(category), Some(&key), format!("Duplicate name \"{}\" (also used by \"{}\")", name, other));
+                }
+            }
+
+            if let Some(blueprint) = entry.get("Blueprint").and_then(Value::as_str).filter(|b| !b.trim().is_empty()) {
+                let owner = (category.to_string(), key.clone());
+                if let Some((other_category, other_key)) = blueprint_owners.insert(blueprint.to_string(), owner) {
+                    report.warning(Some(category), Some(&key), format!("Duplicate blueprint (also used by {} \"{}\")", other_category, other_key));
+                }
+            }
+
+            valid.insert(key, entry);
+        }
+
+        report.loaded.insert(category.to_string(), valid.len());
+        catalog.insert(category.to_string(), Value::Object(valid));
+    }
+
+    for (unknown, _) in root {
+        report.warning(Some(&unknown), None, format!("Unknown section \"{}\" ignored", unknown));
+    }
+
+    Value::Object(catalog)
+}
+
+fn entry_schema_problems(entry: &Value, required: &[&str]) -> Vec<String> {
+    let fields = match entry.as_object() {
+        Some(fields) => fields,
+        None => return vec!["Entry must be an object".to_string()],
+    };
+
+    let mut problems = Vec::new();
+    for field in required {
+        match fields.get(*field) {
+            None | Some(Value::Null) => problems.push(format!("Missing required field \"{}\"", field)),
+            Some(value) if *field == "ID" => {
+                if value.as_i64().is_none() {
+                    problems.push("Field \"ID\" must be an integer".to_string());
+                }
+            }
+            Some(Value::String(s)) => {
+                if *field == "Name" && s.trim().is_empty() {
+                    problems.push("Field \"Name\" must not be empty".to_string());
+                }
+            }
+            Some(_) => problems.push(format!("Field \"{}\" must be a string", field)),
+        }
+    }
+    problems
+}
+
+// Compares dotted version strings numerically, e.g. "1.10.0" > "1.9.2"
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let parse = |v: &str| -> Vec<u64> {
+        v.split(|c: char| c == '.' || c == '-')
+            .map(|part| part.trim().parse::<u64>().unwrap_or(0))
+            .collect()
+    };
+    let (a, b) = (parse(a), parse(b));
+    for i in 0..a.len().max(b.len()) {
+        match a.get(i).unwrap_or(&0).cmp(b.get(i).unwrap_or(&0)) {
+            Ordering::Equal => continue,
+            other => return other,
+        }
+    }
+    Ordering::Equal
+}
+
+fn compare_optional_versions(a: &Option<String>, b: &Option<String>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => compare_versions(a, b),
+        (Some(_), None) => Ordering::Greater,
+        (None, Some(_)) => Ordering::Less,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+pub fn bundled_catalog_path(app: &AppHandle) -> Result<PathBuf, String> {
+    app.path_resolver()
         .resolve_resource("ark_data.json")
-        .expect("failed to resolve resource");
-    
-    let data = fs::read_to_string(resource_path)
-        .map_err(|e| format!("Error reading file: {}", e))?;
-    
-    let mut json_data: Value = serde_json::from_str(&data)
-        .map_err(|e| format!("Error parsing JSON: {}", e))?;
+        .ok_or_else(|| "Failed to resolve ark_data.json resource".to_string())
+}
+
+// Replacement catalogs are picked up from <app data>/catalogs/*.json
+pub fn catalog_override_dir(app: &AppHandle) -> Option<PathBuf> {
+    app.path_resolver().app_data_dir().map(|dir| dir.join("catalogs"))
+}
+
+fn catalog_candidates(app: &AppHandle) -> Vec<(PathBuf, bool)> {
+    let mut candidates = Vec::new();
+    if let Ok(path) = bundled_catalog_path(app) {
+        candidates.push((path, true));
+    }
 
-    // Ensure the JSON structure includes a "Beacons" field
-    if !json_data.as_object().unwrap().contains_key("Beacons") {
-        json_data.as_object_mut().unwrap().insert("Beacons".to_string(), serde_json::json!({}));
+    if let Some(dir) = catalog_override_dir(app) {
+        if let Ok(entries) = fs::read_dir(&dir) {
+            let mut paths: Vec<PathBuf> = entries
+                .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+                .filter(|path| path.extension().map_or(false, |ext| ext == "json"))
+                .collect();
+            paths.sort();
+            candidates.extend(paths.into_iter().map(|path| (path, false)));
+        }
     }
 
-    Ok(json_data)
-}
\ No newline at end of file
+    candidates
+}
+
+fn load_candidates(app: &AppHandle) -> Vec<(CatalogSource, CatalogLoad)> {
+    let mut loads: Vec<(CatalogSource, CatalogLoad)> = catalog_candidates(app)
+        .into_iter()
+        .map(|(path, bundled)| {
+            let load = load_ark_data(&path);
+            let source = CatalogSource {
+                path: path.to_string_lossy().into_owned(),
+                version: load.report.version.clone(),
+                bundled,
+                usable: load.is_usable(),
+                active: false,
+            };
+            (source, load)
+        })
+        .collect();
+
+    // Highest usable version wins; on a tie the dropped-in file beats the bundled one
+    let active = loads
+        .iter()
+        .enumerate()
+        .filter(|(_, (source, _))| source.usable)
+        .max_by(|(_, (a, _)), (_, (b, _))| compare_optional_versions(&a.version, &b.version))
+        .map(|(index, _)| index);
+    if let Some(index) = active {
+        loads[index].0.active = true;
+    }
+
+    loads
+}
+
+pub fn list_catalog_sources(app: &AppHandle) -> Vec<CatalogSource> {
+    load_candidates(app).into_iter().map(|(source, _)| source).collect()
+}
+
+pub fn load_ark_data_from_app(app: &AppHandle) -> CatalogLoad {
+    let mut loads = load_candidates(app);
+    let active = loads.iter().position(|(source, _)| source.active);
+
+    match active {
+        Some(index) => loads.swap_remove(index).1,
+        None => match loads.into_iter().next() {
+            Some((_, load)) => load,
+            None => {
+                let mut report = CatalogLoadReport::default();
+                report.error(None, None, "Failed to resolve ark_data.json resource".to_string());
+                CatalogLoad { data: empty_catalog(), report }
+            }
+        },
+    }
+}
+
+pub fn read_ark_data(app: AppHandle) -> Result<Value, String> {
+    Ok(load_ark_data_from_app(&app).data)
+}
@@ -0,0 +1,122 @@
+// src-tauri/src/beacon_maps.rs
+
+use std::collections::{BTreeSet, HashMap};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+#[derive(Serialize, Debug, Clone)]
+pub struct CatalogBeacon {
+    pub key: String,
+    pub name: String,
+    pub class_name: String,
+    // Parsed from the "[Map] ..." name prefix
+    pub map: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct BeaconMapIssue {
+    pub key: String,
+    pub class_name: String,
+    pub target_map: String,
+    pub beacon_maps: Vec<String>,
+    pub message: String,
+}
+
+// "[Scorched Earth] Desert Crate" -> Some("Scorched Earth")
+pub fn beacon_map(name: &str) -> Option<String> {
+    let rest = name.trim().strip_prefix('[')?;
+    let end = rest.find(']')?;
+    let map = rest[..end].trim();
+    if map.is_empty() { None } else { Some(map.to_string()) }
+}
+
+pub fn catalog_beacons(catalog: &Value) -> Vec<CatalogBeacon> {
+    catalog
+        .get("Beacons")
+        .and_then(Value::as_object)
+        .map(|beacons| {
+            beacons
+                .iter()
+                .map(|(key, beacon)| {
+                    let name = beacon.get("Name").and_then(Value::as_str).unwrap_or_default().to_string();
+                    CatalogBeacon {
+                        key: key.clone(),
+                        map: beacon_map(&name),
+                        class_name: beacon.get("ClassName").and_then(Value::as_str).unwrap_or_default().to_string(),
+                        name,
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+pub fn catalog_maps(catalog: &Value) -> Vec<String> {
+    let maps: BTreeSet<String> = catalog_beacons(catalog).into_iter().filter_map(|beacon| beacon.map).collect();
+    maps.into_iter().collect()
+}
+
+// Lowercased class name -> every map that has a beacon with that class.
+// Some supply crates are shared between maps, so a class can list several.
+fn class_maps(catalog: &Value) -> HashMap<String, Vec<String>> {
+    let mut maps: HashMap<String, Vec<String>> = HashMap::new();
+    for beacon in catalog_beacons(catalog) {
+        if let Some(map) = beacon.map {
+            let entry = maps.entry(beacon.class_name.trim().to_lowercase()).or_default();
+            if !entry.contains(&map) {
+                entry.push(map);
+            }
+        }
+    }
+    maps
+}
+
+// Beacon entries whose catalog maps don't include the target map.
+// Beacons the catalog doesn't know, or knows without a map, are left alone.
+pub fn check_beacon_maps(config: &Value, catalog: &Value, target_map: &str) -> Vec<BeaconMapIssue> {
+    let maps = class_maps(catalog);
+    let mut issues = Vec::new();
+
+    if let Some(entries) = config.get("ShopItems").and_then(Value::as_object) {
+        for (key, entry) in entries {
+            if entry.get("Type").and_then(Value::as_str) != Some("beacon") {
+                continue;
+            }
+            let class_name = entry.get("ClassName").and_then(Value::as_str).unwrap_or_default();
+            let beacon_maps = match maps.get(&class_name.trim().to_lowercase()) {
+                Some(beacon_maps) => beacon_maps,
+                None => continue,
+            };
+            if beacon_maps.iter().any(|map| map.eq_ignore_ascii_case(target_map.trim())) {
+                continue;
+            }
+
+            issues.push(BeaconMapIssue {
+                key: key.clone(),
+                class_name: class_name.to_string(),
+                target_map: target_map.to_string(),
+                message: format!("Beacon \"{}\" only exists on {}, not {}", key, beacon_maps.join(", "), target_map),
+                beacon_maps: beacon_maps.clone(),
+            });
+        }
+    }
+
+    issues
+}
+
+// Removes map-incompatible beacon entries from ShopItems, keeping the order of the rest
+pub fn drop_incompatible_beacons(config: &mut Value, catalog: &Value, target_map: &str) -> Vec<String> {
+    let dropped: Vec<String> = check_beacon_maps(config, catalog, target_map).into_iter().map(|issue| issue.key).collect();
+    if dropped.is_empty() {
+        return dropped;
+    }
+
+    if let Some(entries) = config.get_mut("ShopItems").and_then(Value::as_object_mut) {
+        let kept: Map<String, Value> = std::mem::take(entries)
+            .into_iter()
+            .filter(|(key, _)| !dropped.contains(key))
+            .collect();
+        *entries = kept;
+    }
+    dropped
+}
@@ -0,0 +1,316 @@
+// src-tauri/src/blueprint.rs
+
+use std::collections::HashMap;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use crate::config_refs::blueprint_refs;
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ParsedBlueprint {
+    // Package directory, e.g. "/Game/PrimalEarth/Dinos/Raptor"
+    pub package: String,
+    // Asset name, which is also the object name in canonical form
+    pub asset: String,
+}
+
+impl ParsedBlueprint {
+    pub fn canonical(&self) -> String {
+        format!("Blueprint'{}/{}.{}'", self.package, self.asset, self.asset)
+    }
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BlueprintProblem {
+    Empty,
+    MismatchedQuotes,
+    MissingPackage,
+    WrongObjectSuffix,
+    UnknownClass,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct BlueprintError {
+    pub problem: BlueprintProblem,
+    pub message: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct BlueprintFix {
+    pub pointer: String,
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct BlueprintIssue {
+    pub pointer: String,
+    pub value: String,
+    pub problem: BlueprintProblem,
+    pub message: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct NormalizeReport {
+    pub config: Value,
+    pub fixed: Vec<BlueprintFix>,
+    pub issues: Vec<BlueprintIssue>,
+}
+
+fn error(problem: BlueprintProblem, message: String) -> BlueprintError {
+    BlueprintError { problem, message }
+}
+
+fn class_key(name: &str) -> String {
+    let name = name.trim();
+    name.strip_suffix("_C").unwrap_or(name).to_lowercase()
+}
+
+// Maps bare class names (with or without _C) to canonical catalog blueprints
+pub fn class_index(catalog: &Value) -> HashMap<String, String> {
+    let mut index = HashMap::new();
+    for section in ["Items", "Dinos", "Engrams"] {
+        if let Some(entries) = catalog.get(section).and_then(Value::as_object) {
+            for entry in entries.values() {
+                if let Some(Ok(parsed)) = entry.get("Blueprint").and_then(Value::as_str).map(parse_blueprint) {
+                    index.entry(class_key(&parsed.asset)).or_insert_with(|| parsed.canonical());
+                }
+            }
+        }
+    }
+    index
+}
+
+// Unwraps a Type'...' reference or surrounding double quotes, returning the inner path
+fn unwrap_reference(raw: &str) -> Result<&str, BlueprintError> {
+    let mut text = raw.trim();
+
+    let opens = text.starts_with('"');
+    let closes = text.len() > 1 && text.ends_with('"');
+    if opens != closes {
+        return Err(error(BlueprintProblem::MismatchedQuotes, "Double quotes around the blueprint don't match".to_string()));
+    }
+    if opens {
+        text = text[1..text.len() - 1].trim();
+    }
+
+    match text.find('\'') {
+        None => Ok(text),
+        Some(open) => {
+            let prefix = &text[..open];
+            let rest = &text[open + 1..];
+            if !prefix.chars().all(|c| c.is_ascii_alphanumeric()) {
+                return Err(error(BlueprintProblem::MismatchedQuotes, format!("Unexpected text \"{}\" before the opening quote", prefix)));
+            }
+            match rest.find('\'') {
+                Some(close) if close == rest.len() - 1 => Ok(rest[..close].trim()),
+                Some(_) => Err(error(BlueprintProblem::MismatchedQuotes, "Text follows the closing quote".to_string())),
+                None => Err(error(BlueprintProblem::MismatchedQuotes, "The opening quote is never closed".to_string())),
+            }
+        }
+    }
+}
+
+pub fn parse_blueprint(raw: &str) -> Result<ParsedBlueprint, BlueprintError> {
+    if raw.trim().is_empty() {
+        return Err(error(BlueprintProblem::Empty, "Blueprint is empty".to_string()));
+    }
+
+    let inner = unwrap_reference(raw)?;
+    if !inner.contains('/') {
+        return Err(error(BlueprintProblem::MissingPackage, format!("\"{}\" is a class name without a package path", inner)));
+    }
+
+    let path = match inner.strip_prefix("Game/") {
+        Some(rest) => format!("/Game/{}", rest),
+        None => inner.to_string(),
+    };
+    if !path.starts_with('/') {
+        return Err(error(BlueprintProblem::MissingPackage, format!("\"{}\" doesn't start with a package such as /Game/", inner)));
+    }
+
+    let split = path.rfind('/').unwrap_or(0);
+    let (package, leaf) = (&path[..split], &path[split + 1..]);
+    let (asset, object) = match leaf.split_once('.') {
+        Some((asset, object)) => (asset, object),
+        None => (leaf, leaf),
+    };
+    if package.is_empty() || asset.is_empty() {
+        return Err(error(BlueprintProblem::MissingPackage, format!("\"{}\" is missing its package or asset name", inner)));
+    }
+
+    let object = object.strip_suffix("_C").unwrap_or(object);
+    if object != asset {
+        return Err(error(
+            BlueprintProblem::WrongObjectSuffix,
+            format!("Object name \"{}\" doesn't match asset \"{}\"", object, asset),
+        ));
+    }
+
+    Ok(ParsedBlueprint {
+        package: package.to_string(),
+        asset: asset.to_string(),
+    })
+}
+
+// Canonical form of a blueprint, resolving bare class names through the catalog index
+pub fn normalize_blueprint(raw: &str, classes: &HashMap<String, String>) -> Result<String, BlueprintError> {
+    match parse_blueprint(raw) {
+        Ok(parsed) => Ok(parsed.canonical()),
+        Err(err) if err.problem == BlueprintProblem::MissingPackage => {
+            let inner = unwrap_reference(raw)?;
+            if inner.contains('/') {
+                return Err(err);
+            }
+            classes.get(&class_key(inner)).cloned().ok_or_else(|| {
+                error(BlueprintProblem::UnknownClass, format!("Class \"{}\" isn't in the catalog, use its full blueprint path", inner))
+            })
+        }
+        Err(err) => Err(err),
+    }
+}
+
+// Renames one key of the map at `parent`, keeping its position
+fn rename_key(config: &mut Value, parent: &str, from: &str, to: &str) -> bool {
+    if let Some(map) = config.pointer_mut(parent).and_then(Value::as_object_mut) {
+        if map.contains_key(to) {
+            return false;
+        }
+        let renamed: Map<String, Value> = std::mem::take(map)
+            .into_iter()
+            .map(|(key, value)| if key == from { (to.to_string(), value) } else { (key, value) })
+            .collect();
+        *map = renamed;
+        return true;
+    }
+    false
+}
+
+// Rewrites every blueprint in ShopItems, Kits, SellItems and General.CryoItemPath
+// to canonical form, reporting the ones that can't be parsed
+pub fn normalize_config_blueprints(config: &mut Value, classes: &HashMap<String, String>) -> (Vec<BlueprintFix>, Vec<BlueprintIssue>) {
+    let mut fixed = Vec::new();
+    let mut issues = Vec::new();
+
+    for reference in blueprint_refs(config) {
+        let canonical = match normalize_blueprint(&reference.value, classes) {
+            Ok(canonical) => canonical,
+            Err(err) => {
+                issues.push(BlueprintIssue {
+                    pointer: reference.pointer,
+                    value: reference.value,
+                    problem: err.problem,
+                    message: err.message,
+                });
+                continue;
+            }
+        };
+        if canonical == reference.value {
+            continue;
+        }
+
+        if reference.is_key {
+            // Two spellings of the same resource can't both be kept as keys
+            let parent = &reference.pointer[..reference.pointer.rfind('/').unwrap_or(0)];
+            if !rename_key(config, parent, &reference.value, &canonical) {
+                continue;
+            }
+        } else if let Some(field) = config.pointer_mut(&reference.pointer) {
+            *field = Value::String(canonical.clone());
+        }
+        fixed.push(BlueprintFix {
+            pointer: reference.pointer,
+            from: reference.value,
+            to: canonical,
+        });
+    }
+
+    (fixed, issues)
+}
+
+pub fn validate_config_blueprints(config: &Value, classes: &HashMap<String, String>) -> Vec<BlueprintIssue> {
+    blueprint_refs(config)
+        .into_iter()
+        .filter_map(|reference| {
+            normalize_blueprint(&reference.value, classes).err().map(|err| BlueprintIssue {
+                pointer: reference.pointer,
+                value: reference.value,
+                problem: err.problem,
+                message: err.message,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const RAPTOR: &str = "Blueprint'/Game/PrimalEarth/Dinos/Raptor/Raptor_Character_BP.Raptor_Character_BP'";
+
+    fn classes() -> HashMap<String, String> {
+        class_index(&json!({
+            "Dinos": {
+                "Raptor": { "Blueprint": "/Game/PrimalEarth/Dinos/Raptor/Raptor_Character_BP.Raptor_Character_BP_C" }
+            }
+        }))
+    }
+
+    #[test]
+    fn normalizes_every_accepted_spelling() {
+        let cases = [
+            RAPTOR,
+            "BlueprintGeneratedClass'/Game/PrimalEarth/Dinos/Raptor/Raptor_Character_BP.Raptor_Character_BP_C'",
+            "\"Blueprint'/Game/PrimalEarth/Dinos/Raptor/Raptor_Character_BP.Raptor_Character_BP'\"",
+            "/Game/PrimalEarth/Dinos/Raptor/Raptor_Character_BP.Raptor_Character_BP",
+            "/Game/PrimalEarth/Dinos/Raptor/Raptor_Character_BP.Raptor_Character_BP_C",
+            "/Game/PrimalEarth/Dinos/Raptor/Raptor_Character_BP",
+            "Game/PrimalEarth/Dinos/Raptor/Raptor_Character_BP.Raptor_Character_BP",
+            "Raptor_Character_BP_C",
+            "raptor_character_bp",
+            "  Blueprint' /Game/PrimalEarth/Dinos/Raptor/Raptor_Character_BP.Raptor_Character_BP '  ",
+            "\t/Game/PrimalEarth/Dinos/Raptor/Raptor_Character_BP.Raptor_Character_BP_C\n",
+        ];
+        for raw in cases {
+            match normalize_blueprint(raw, &classes()) {
+                Ok(canonical) => assert_eq!(canonical, RAPTOR, "{:?}", raw),
+                Err(err) => panic!("{:?}: {}", raw, err.message),
+            }
+        }
+    }
+
+    #[test]
+    fn reports_malformed_blueprints() {
+        let cases = [
+            ("", BlueprintProblem::Empty),
+            ("   ", BlueprintProblem::Empty),
+            ("\"Blueprint'/Game/Mods/Foo/Bar.Bar'", BlueprintProblem::MismatchedQuotes),
+            ("Blueprint'/Game/Mods/Foo/Bar.Bar'\"", BlueprintProblem::MismatchedQuotes),
+            ("Blueprint'/Game/Mods/Foo/Bar.Bar", BlueprintProblem::MismatchedQuotes),
+            ("Blueprint'/Game/Mods/Foo/Bar.Bar'_C", BlueprintProblem::MismatchedQuotes),
+            ("Blue print'/Game/Mods/Foo/Bar.Bar'", BlueprintProblem::MismatchedQuotes),
+            ("/Game/Mods/Foo/Bar.Baz", BlueprintProblem::WrongObjectSuffix),
+            ("/Game/Mods/Foo/Bar.Baz_C", BlueprintProblem::WrongObjectSuffix),
+            ("Blueprint'/Game/Mods/Foo/Bar.Bar_C_C'", BlueprintProblem::WrongObjectSuffix),
+            ("Mods/Foo/Bar.Bar", BlueprintProblem::MissingPackage),
+            ("/Bar.Bar", BlueprintProblem::MissingPackage),
+            ("/Game/Mods/Foo/", BlueprintProblem::MissingPackage),
+            ("Dodo_Character_BP_C", BlueprintProblem::UnknownClass),
+        ];
+        for (raw, problem) in cases {
+            match normalize_blueprint(raw, &classes()) {
+                Ok(canonical) => panic!("{:?} normalized to {}", raw, canonical),
+                Err(err) => assert_eq!(err.problem, problem, "{:?}: {}", raw, err.message),
+            }
+        }
+    }
+
+    #[test]
+    fn parse_needs_a_package_even_for_known_classes() {
+        assert_eq!(parse_blueprint("Raptor_Character_BP_C").unwrap_err().problem, BlueprintProblem::MissingPackage);
+        let parsed = parse_blueprint("/Game/PrimalEarth/Dinos/Raptor/Raptor_Character_BP.Raptor_Character_BP_C").unwrap();
+        assert_eq!(parsed.package, "/Game/PrimalEarth/Dinos/Raptor");
+        assert_eq!(parsed.asset, "Raptor_Character_BP");
+    }
+}
@@ -0,0 +1,281 @@
+// src-tauri/src/bundle.rs
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs::File;
+use std::io::{Read, Write};
+use std::path::Path;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+use crate::ark_data::CATALOG_SCHEMA;
+use crate::blueprint::parse_blueprint;
+use crate::config_refs::{beacon_class_refs, blueprint_refs};
+
+pub const BUNDLE_EXTENSION: &str = "arkshopbundle";
+const BUNDLE_FORMAT: u32 = 1;
+
+const CONFIG_FILE: &str = "config.json";
+const METADATA_FILE: &str = "metadata.json";
+const CATALOG_FILE: &str = "catalog.json";
+const CHECKSUM_FILE: &str = "checksums.sha256";
+
+// Server credentials and webhooks never leave in a bundle
+pub const REDACTED_FIELDS: [&str; 4] = ["/Mysql/MysqlHost", "/Mysql/MysqlUser", "/Mysql/MysqlPass", "/General/Discord/URL"];
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BundleMetadata {
+    pub format: u32,
+    pub app_version: String,
+    pub catalog_version: Option<String>,
+    pub source_name: String,
+    pub created_at: DateTime<Utc>,
+    // Pointers of fields that were blanked on export
+    pub redacted: Vec<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SharedBundle {
+    pub config: Value,
+    pub metadata: BundleMetadata,
+    // Catalog entries the config uses that aren't in the bundled ark_data.json, by section
+    pub custom_catalog: Value,
+}
+
+// Blanks every non-empty redacted field and returns their pointers
+pub fn redact_config(config: &mut Value) -> Vec<String> {
+    let mut redacted = Vec::new();
+    for pointer in REDACTED_FIELDS.iter() {
+        if let Some(field) = config.pointer_mut(pointer) {
+            if field.as_str().map_or(true, |text| !text.is_empty()) {
+                *field = Value::String(String::new());
+                redacted.push(pointer.to_string());
+            }
+        }
+    }
+    redacted
+}
+
+fn reference_key(value: &str) -> String {
+    parse_blueprint(value).map_or_else(|_| value.trim().to_lowercase(), |parsed| parsed.canonical().to_lowercase())
+}
+
+// Entries of the active catalog that the config references and the bundled catalog lacks
+pub fn custom_catalog_entries(config: &Value, active: &Value, bundled: &Value) -> Value {
+    let used: HashSet<String> = blueprint_refs(config)
+        .into_iter()
+        .chain(beacon_class_refs(config))
+        .map(|reference| reference_key(&reference.value))
+        .collect();
+
+    let mut custom = Map::new();
+    for (category, _) in CATALOG_SCHEMA.iter() {
+        let field = if *category == "Beacons" { "ClassName" } else { "Blueprint" };
+        let reference = |entry: &Value| entry.get(field).and_then(Value::as_str).map(reference_key);
+
+        let stock: HashSet<String> = bundled
+            .get(*category)
+            .and_then(Value::as_object)
+            .map(|entries| entries.values().filter_map(reference).collect())
+            .unwrap_or_default();
+
+        let entries: Map<String, Value> = active
+            .get(*category)
+            .and_then(Value::as_object)
+            .map(|entries| {
+                entries
+                    .iter()
+                    .filter(|(_, entry)| reference(entry).map_or(false, |key| used.contains(&key) && !stock.contains(&key)))
+                    .map(|(key, entry)| (key.clone(), entry.clone()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        if !entries.is_empty() {
+            custom.insert(category.to_string(), Value::Object(entries));
+        }
+    }
+    Value::Object(custom)
+}
+
+fn digest(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+// Zips the files along with a checksums.sha256 in the same layout as sha256sum output
+pub fn write_archive(path: &Path, files: &[(String, Vec<u8>)]) -> Result<(), String> {
+    let checksums: String = files
+        .iter()
+        .map(|(name, bytes)| format!("{}  {}\n", digest(bytes), name))
+        .collect();
+
+    let file = File::create(path).map_err(|e| format!("Failed to create archive: {}", e))?;
+    let mut zip = ZipWriter::new(file);
+    let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+    for (name, bytes) in files.iter().map(|(name, bytes)| (name.as_str(), bytes.as_slice())).chain(std::iter::once((CHECKSUM_FILE, checksums.as_bytes()))) {
+        zip.start_file(name, options).map_err(|e| e.to_string())?;
+        zip.write_all(bytes).map_err(|e| e.to_string())?;
+    }
+    zip.finish().map_err(|e| format!("Failed to write archive: {}", e))?;
+    Ok(())
+}
+
+// Every file listed in checksums.sha256, verified; anything unlisted is ignored
+pub fn read_archive(path: &Path) -> Result<BTreeMap<String, Vec<u8>>, String> {
+    let file = File::open(path).map_err(|e| format!("Failed to open archive: {}", e))?;
+    let mut archive = ZipArchive::new(file).map_err(|e| format!("Not a valid archive: {}", e))?;
+
+    let mut read_entry = |name: &str| -> Result<Vec<u8>, String> {
+        let mut entry = archive.by_name(name).map_err(|_| format!("Archive is missing {}", name))?;
+        let mut bytes = Vec::new();
+        entry.read_to_end(&mut bytes).map_err(|e| format!("Failed to read {}: {}", name, e))?;
+        Ok(bytes)
+    };
+
+    let checksums = String::from_utf8(read_entry(CHECKSUM_FILE)?).map_err(|e| e.to_string())?;
+    let mut files = BTreeMap::new();
+    for (hash, name) in checksums.lines().filter_map(|line| line.split_once("  ")) {
+        let name = name.trim();
+        let bytes = read_entry(name)?;
+        if digest(&bytes) != hash.trim().to_lowercase() {
+            return Err(format!("Checksum mismatch for {}, the archive is damaged or was modified", name));
+        }
+        files.insert(name.to_string(), bytes);
+    }
+    Ok(files)
+}
+
+// The config is expected to be redacted already
+pub fn write_bundle(path: &Path, config: &Value, metadata: &BundleMetadata, custom_catalog: &Value) -> Result<(), String> {
+    let files = vec![
+        (CONFIG_FILE.to_string(), serde_json::to_vec_pretty(config).map_err(|e| e.to_string())?),
+        (METADATA_FILE.to_string(), serde_json::to_vec_pretty(metadata).map_err(|e| e.to_string())?),
+        (CATALOG_FILE.to_string(), serde_json::to_vec_pretty(custom_catalog).map_err(|e| e.to_string())?),
+    ];
+    write_archive(path, &files)
+}
+
+pub fn read_bundle(path: &Path) -> Result<SharedBundle, String> {
+    let files = read_archive(path)?;
+    let file = |name: &str| -> Result<&Vec<u8>, String> {
+        files.get(name).ok_or_else(|| format!("Bundle has no checksum for {}", name))
+    };
+
+    let config: Value = serde_json::from_slice(file(CONFIG_FILE)?).map_err(|e| format!("Failed to parse {}: {}", CONFIG_FILE, e))?;
+    if !config.is_object() {
+        return Err("Bundled config must be a JSON object".to_string());
+    }
+    let metadata: BundleMetadata = serde_json::from_slice(file(METADATA_FILE)?).map_err(|e| format!("Failed to parse {}: {}", METADATA_FILE, e))?;
+    if metadata.format > BUNDLE_FORMAT {
+        return Err(format!("Bundle format {} is newer than this app supports, please update", metadata.format));
+    }
+    let custom_catalog: Value = serde_json::from_slice(file(CATALOG_FILE)?).map_err(|e| format!("Failed to parse {}: {}", CATALOG_FILE, e))?;
+
+    Ok(SharedBundle { config, metadata, custom_catalog })
+}
+
+pub fn new_metadata(app_version: String, catalog_version: Option<String>, source_name: String, redacted: Vec<String>) -> BundleMetadata {
+    BundleMetadata {
+        format: BUNDLE_FORMAT,
+        app_version,
+        catalog_version,
+        source_name,
+        created_at: Utc::now(),
+        redacted,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn config() -> Value {
+        json!({
+            "Mysql": { "UseMysql": true, "MysqlHost": "10.0.0.5", "MysqlUser": "ark", "MysqlPass": "hunter2", "MysqlDB": "arkshop", "MysqlPort": 3306 },
+            "General": { "Discord": { "Enabled": true, "SenderName": "Shop", "URL": "https://discord.com/api/webhooks/1/secret" } },
+            "ShopItems": {
+                "raptor": { "Type": "dino", "Price": 100, "Level": 150, "Blueprint": "Blueprint'/Game/Mods/Custom/Raptor/Raptor_BP.Raptor_BP'" }
+            }
+        })
+    }
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("arkshop-{}-{}.{}", name, std::process::id(), BUNDLE_EXTENSION))
+    }
+
+    fn exported(path: &Path) -> (Value, BundleMetadata) {
+        let mut config = config();
+        let redacted = redact_config(&mut config);
+        let metadata = new_metadata("1.0.0".to_string(), Some("2024.1".to_string()), "Main server".to_string(), redacted);
+        let custom_catalog = json!({ "Dinos": { "Custom Raptor": { "Blueprint": "Blueprint'/Game/Mods/Custom/Raptor/Raptor_BP.Raptor_BP'" } } });
+        write_bundle(path, &config, &metadata, &custom_catalog).unwrap();
+        (config, metadata)
+    }
+
+    fn zip_entries(path: &Path) -> Vec<(String, Vec<u8>)> {
+        let mut archive = ZipArchive::new(File::open(path).unwrap()).unwrap();
+        (0..archive.len())
+            .map(|index| {
+                let mut entry = archive.by_index(index).unwrap();
+                let mut bytes = Vec::new();
+                entry.read_to_end(&mut bytes).unwrap();
+                (entry.name().to_string(), bytes)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn round_trips_a_bundle() {
+        let path = temp_path("bundle-round-trip");
+        let (config, metadata) = exported(&path);
+
+        let bundle = read_bundle(&path).unwrap();
+        assert_eq!(bundle.config, config);
+        assert_eq!(bundle.metadata.source_name, "Main server");
+        assert_eq!(bundle.metadata.catalog_version, metadata.catalog_version);
+        assert_eq!(bundle.metadata.created_at, metadata.created_at);
+        assert_eq!(bundle.metadata.redacted, REDACTED_FIELDS.to_vec());
+        assert!(bundle.custom_catalog["Dinos"]["Custom Raptor"].is_object());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn rejects_a_tampered_bundle() {
+        let path = temp_path("bundle-tampered");
+        exported(&path);
+
+        // Rezip with one byte of config.json changed but the original checksums kept
+        let mut files = zip_entries(&path);
+        let (_, bytes) = files.iter_mut().find(|(name, _)| name == CONFIG_FILE).unwrap();
+        let position = bytes.windows(3).position(|window| window == b"100").unwrap();
+        bytes[position] = b'0';
+        let mut zip = ZipWriter::new(File::create(&path).unwrap());
+        for (name, bytes) in &files {
+            zip.start_file(name.as_str(), FileOptions::default()).unwrap();
+            zip.write_all(bytes).unwrap();
+        }
+        zip.finish().unwrap();
+
+        let err = read_bundle(&path).unwrap_err();
+        assert_eq!(err, "Checksum mismatch for config.json, the archive is damaged or was modified");
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn written_config_has_every_redacted_field_blank() {
+        let path = temp_path("bundle-redacted");
+        exported(&path);
+
+        let files = zip_entries(&path);
+        let (_, bytes) = files.iter().find(|(name, _)| name == CONFIG_FILE).unwrap();
+        let written: Value = serde_json::from_slice(bytes).unwrap();
+        for pointer in REDACTED_FIELDS.iter() {
+            assert_eq!(written.pointer(pointer), Some(&json!("")), "{}", pointer);
+        }
+        assert_eq!(written["Mysql"]["MysqlDB"], "arkshop");
+        assert!(!String::from_utf8_lossy(bytes).contains("hunter2"));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
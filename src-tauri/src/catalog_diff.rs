@@ -0,0 +1,191 @@
+// src-tauri/src/catalog_diff.rs
+
+use std::collections::HashMap;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use crate::ark_data::CATALOG_SCHEMA;
+use crate::config_refs::{beacon_class_refs, blueprint_refs, CatalogRef};
+
+#[derive(Serialize, Debug, Clone)]
+pub struct BlueprintChange {
+    pub category: String,
+    pub key: String,
+    pub name: Option<String>,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
+#[derive(Serialize, Debug, Default)]
+pub struct CatalogDiff {
+    pub from_version: Option<String>,
+    pub to_version: Option<String>,
+    pub added: Vec<BlueprintChange>,
+    pub removed: Vec<BlueprintChange>,
+    pub changed: Vec<BlueprintChange>,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImpactStatus {
+    Removed,
+    Moved,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct AffectedReference {
+    pub pointer: String,
+    pub value: String,
+    pub status: ImpactStatus,
+    pub replacement: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ConfigImpact {
+    pub config_id: Option<i64>,
+    pub config_name: String,
+    pub references: Vec<AffectedReference>,
+    // Set when the saved config isn't valid JSON and couldn't be checked
+    pub parse_error: Option<String>,
+}
+
+// Beacons are identified by their class name, everything else by blueprint
+fn reference_field(category: &str) -> &'static str {
+    if category == "Beacons" {
+        "ClassName"
+    } else {
+        "Blueprint"
+    }
+}
+
+fn entries<'a>(catalog: &'a Value, category: &str) -> Option<&'a Map<String, Value>> {
+    catalog.get(category).and_then(Value::as_object)
+}
+
+fn field(entry: &Value, name: &str) -> Option<String> {
+    entry.get(name).and_then(Value::as_str).map(|s| s.trim().to_string())
+}
+
+pub fn diff_catalogs(old: &Value, new: &Value) -> CatalogDiff {
+    let empty = Map::new();
+    let mut diff = CatalogDiff {
+        from_version: field(old, "Version"),
+        to_version: field(new, "Version"),
+        ..Default::default()
+    };
+
+    for (category, _) in CATALOG_SCHEMA.iter() {
+        let ref_field = reference_field(category);
+        let old_entries = entries(old, category).unwrap_or(&empty);
+        let new_entries = entries(new, category).unwrap_or(&empty);
+
+        let new_values: HashMap<String, &String> = new_entries
+            .iter()
+            .filter_map(|(key, entry)| field(entry, ref_field).map(|value| (value, key)))
+            .collect();
+        let new_by_name: HashMap<String, &String> = new_entries
+            .iter()
+            .filter_map(|(key, entry)| field(entry, "Name").map(|name| (name.to_lowercase(), key)))
+            .collect();
+
+        for (key, old_entry) in old_entries {
+            let old_value = field(old_entry, ref_field);
+            let name = field(old_entry, "Name");
+
+            let counterpart = new_entries.get(key).map(|entry| (key, entry)).or_else(|| {
+                // A renamed key keeps its identity through the display name
+                name.as_ref()
+                    .and_then(|name| new_by_name.get(&name.to_lowercase()))
+                    .and_then(|new_key| new_entries.get(*new_key).map(|entry| (*new_key, entry)))
+            });
+
+            match counterpart {
+                Some((new_key, new_entry)) => {
+                    let new_value = field(new_entry, ref_field);
+                    if new_value != old_value {
+                        diff.changed.push(BlueprintChange {
+                            category: category.to_string(),
+                            key: new_key.clone(),
+                            name,
+                            old_value,
+                            new_value,
+                        });
+                    }
+                }
+                None => {
+                    let still_present = old_value.as_ref().map_or(false, |value| new_values.contains_key(value));
+                    if !still_present {
+                        diff.removed.push(BlueprintChange {
+                            category: category.to_string(),
+                            key: key.clone(),
+                            name,
+                            old_value,
+                            new_value: None,
+                        });
+                    }
+                }
+            }
+        }
+
+        let old_values: HashMap<String, &String> = old_entries
+            .iter()
+            .filter_map(|(key, entry)| field(entry, ref_field).map(|value| (value, key)))
+            .collect();
+        let changed_keys: Vec<&String> = diff
+            .changed
+            .iter()
+            .filter(|change| change.category == *category)
+            .map(|change| &change.key)
+            .collect();
+
+        for (key, new_entry) in new_entries {
+            if old_entries.contains_key(key) || changed_keys.contains(&key) {
+                continue;
+            }
+            let new_value = field(new_entry, ref_field);
+            if new_value.as_ref().map_or(false, |value| old_values.contains_key(value)) {
+                continue;
+            }
+            diff.added.push(BlueprintChange {
+                category: category.to_string(),
+                key: key.clone(),
+                name: field(new_entry, "Name"),
+                old_value: None,
+                new_value,
+            });
+        }
+    }
+
+    diff
+}
+
+fn affected(refs: Vec<CatalogRef>, changes: &HashMap<String, (ImpactStatus, Option<String>)>) -> Vec<AffectedReference> {
+    refs.into_iter()
+        .filter_map(|reference| {
+            changes.get(reference.value.trim()).map(|(status, replacement)| AffectedReference {
+                pointer: reference.pointer,
+                value: reference.value,
+                status: *status,
+                replacement: replacement.clone(),
+            })
+        })
+        .collect()
+}
+
+pub fn config_impact(config: &Value, diff: &CatalogDiff) -> Vec<AffectedReference> {
+    let mut blueprint_changes = HashMap::new();
+    let mut beacon_changes = HashMap::new();
+
+    for change in diff.removed.iter().chain(diff.changed.iter()) {
+        let old_value = match &change.old_value {
+            Some(value) => value.clone(),
+            None => continue,
+        };
+        let status = if change.new_value.is_some() { ImpactStatus::Moved } else { ImpactStatus::Removed };
+        let target = if change.category == "Beacons" { &mut beacon_changes } else { &mut blueprint_changes };
+        target.insert(old_value, (status, change.new_value.clone()));
+    }
+
+    let mut references = affected(blueprint_refs(config), &blueprint_changes);
+    references.extend(affected(beacon_class_refs(config), &beacon_changes));
+    references
+}
@@ -0,0 +1,418 @@
+// src-tauri/src/catalog_export.rs
+
+use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::blueprint::parse_blueprint;
+use crate::entry_order::{catalog_categories, entry_category};
+use crate::rich_color::strip_rich_text;
+use crate::shop_preview::format_price;
+
+// Discord rejects messages longer than this
+pub const DISCORD_MESSAGE_LIMIT: usize = 2000;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CatalogFormat {
+    Html,
+    Markdown,
+}
+
+// Each template is filled with {placeholders}; {content} is the rendered level below
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CatalogTemplates {
+    // {title}, {content}
+    pub page: String,
+    // {title}, {content}
+    pub section: String,
+    // {title}, {content}
+    pub group: String,
+    // {name}, {key}, {command}, {price}, {details}, {description}
+    pub entry: String,
+}
+
+// Partial override; missing templates fall back to the format's defaults
+#[derive(Deserialize, Debug, Default)]
+pub struct TemplateOverrides {
+    pub page: Option<String>,
+    pub section: Option<String>,
+    pub group: Option<String>,
+    pub entry: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CatalogEntry {
+    pub key: String,
+    pub name: String,
+    pub command: String,
+    pub price: String,
+    pub details: Vec<String>,
+    pub description: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CatalogGroup {
+    pub title: String,
+    pub entries: Vec<CatalogEntry>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CatalogSection {
+    pub title: String,
+    pub groups: Vec<CatalogGroup>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct RenderedCatalog {
+    pub content: String,
+    // Markdown split into Discord-sized messages; empty for HTML
+    pub discord_chunks: Vec<String>,
+}
+
+impl CatalogTemplates {
+    pub fn defaults(format: CatalogFormat) -> Self {
+        match format {
+            CatalogFormat::Html => CatalogTemplates {
+                page: "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n<style>\nbody { font-family: sans-serif; background: #1e1e1e; color: #ddd; }\ntable { border-collapse: collapse; margin-bottom: 1em; }\nth, td { border: 1px solid #444; padding: 4px 8px; text-align: left; }\n</style>\n</head>\n<body>\n<h1>{title}</h1>\n{content}</body>\n</html>\n".to_string(),
+                section: "<h2>{title}</h2>\n{content}".to_string(),
+                group: "<h3>{title}</h3>\n<table>\n<tr><th>Name</th><th>Command</th><th>Price</th><th>Contents</th></tr>\n{content}</table>\n".to_string(),
+                entry: "<tr><td>{name}</td><td><code>{command}</code></td><td>{price}</td><td>{details}</td></tr>\n".to_string(),
+            },
+            CatalogFormat::Markdown => CatalogTemplates {
+                page: "# {title}\n\n{content}".to_string(),
+                section: "## {title}\n\n{content}".to_string(),
+                group: "### {title}\n{content}\n".to_string(),
+                entry: "- **{name}** `{command}` {price} {details}\n".to_string(),
+            },
+        }
+    }
+
+    pub fn with_overrides(format: CatalogFormat, overrides: TemplateOverrides) -> Self {
+        let defaults = CatalogTemplates::defaults(format);
+        CatalogTemplates {
+            page: overrides.page.unwrap_or(defaults.page),
+            section: overrides.section.unwrap_or(defaults.section),
+            group: overrides.group.unwrap_or(defaults.group),
+            entry: overrides.entry.unwrap_or(defaults.entry),
+        }
+    }
+}
+
+// Canonical blueprint or beacon class name -> catalog display name
+pub fn display_names(catalog: &Value) -> HashMap<String, String> {
+    let mut names = HashMap::new();
+    for section in ["Items", "Dinos", "Engrams"] {
+        for entry in catalog.get(section).and_then(Value::as_object).into_iter().flat_map(|entries| entries.values()) {
+            if let (Some(Ok(parsed)), Some(name)) = (
+                entry.get("Blueprint").and_then(Value::as_str).map(parse_blueprint),
+                entry.get("Name").and_then(Value::as_str),
+            ) {
+                names.entry(parsed.canonical()).or_insert_with(|| name.to_string());
+            }
+        }
+    }
+    for beacon in catalog.get("Beacons").and_then(Value::as_object).into_iter().flat_map(|entries| entries.values()) {
+        if let (Some(class_name), Some(name)) = (beacon.get("ClassName").and_then(Value::as_str), beacon.get("Name").and_then(Value::as_str)) {
+            names.entry(class_name.trim().to_string()).or_insert_with(|| name.to_string());
+        }
+    }
+    names
+}
+
+fn display_name(reference: &str, names: &HashMap<String, String>) -> String {
+    let canonical = parse_blueprint(reference).map(|parsed| parsed.canonical()).unwrap_or_else(|_| reference.trim().to_string());
+    names.get(&canonical).cloned().unwrap_or_else(|| {
+        // Fall back to the asset name, which is at least readable
+        parse_blueprint(reference).map(|parsed| parsed.asset).unwrap_or_else(|_| reference.trim().to_string())
+    })
+}
+
+fn text_field(entry: &Value, field: &str) -> Option<String> {
+    entry
+        .get(field)
+        .and_then(Value::as_str)
+        .map(strip_rich_text)
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty())
+}
+
+fn number_field(entry: &Value, field: &str) -> Option<String> {
+    entry.get(field).filter(|value| value.is_number()).map(format_price)
+}
+
+fn contents(entry: &Value, names: &HashMap<String, String>) -> Vec<String> {
+    let mut details = Vec::new();
+
+    for item in entry.get("Items").and_then(Value::as_array).into_iter().flatten() {
+        if let Some(command) = item.get("Command").and_then(Value::as_str) {
+            let label = text_field(item, "DisplayAs").unwrap_or_else(|| command.to_string());
+            details.push(label);
+        } else if let Some(blueprint) = item.get("Blueprint").and_then(Value::as_str) {
+            let name = display_name(blueprint, names);
+            match number_field(item, "Amount") {
+                Some(amount) => details.push(format!("{}x {}", amount, name)),
+                None => details.push(name),
+            }
+        }
+    }
+    for dino in entry.get("Dinos").and_then(Value::as_array).into_iter().flatten() {
+        if let Some(blueprint) = dino.get("Blueprint").and_then(Value::as_str) {
+            let name = display_name(blueprint, names);
+            match number_field(dino, "Level") {
+                Some(level) => details.push(format!("{} (Lvl {})", name, level)),
+                None => details.push(name),
+            }
+        }
+    }
+
+    details
+}
+
+fn shop_entry(key: &str, entry: &Value, names: &HashMap<String, String>) -> CatalogEntry {
+    let entry_type = entry.get("Type").and_then(Value::as_str).unwrap_or_default();
+    let mut details = contents(entry, names);
+    let fallback = match entry_type {
+        "dino" => entry.get("Blueprint").and_then(Value::as_str).map(|blueprint| display_name(blueprint, names)),
+        "beacon" => entry.get("ClassName").and_then(Value::as_str).map(|class_name| display_name(class_name, names)),
+        _ => None,
+    };
+    match entry_type {
+        "dino" => {
+            if let Some(level) = number_field(entry, "Level") {
+                details.push(format!("Level {}", level));
+            }
+        }
+        "experience" => {
+            if let Some(amount) = number_field(entry, "Amount") {
+                details.push(format!("{} XP", amount));
+            }
+        }
+        _ => {}
+    }
+
+    CatalogEntry {
+        key: key.to_string(),
+        name: text_field(entry, "Title").or(fallback).or_else(|| text_field(entry, "Description")).unwrap_or_else(|| key.to_string()),
+        command: format!("/buy {}", key),
+        price: number_field(entry, "Price").unwrap_or_default(),
+        details,
+        description: text_field(entry, "Description").unwrap_or_default(),
+    }
+}
+
+fn type_label(entry_type: &str) -> &'static str {
+    match entry_type {
+        "item" => "Items",
+        "dino" => "Dinos",
+        "beacon" => "Beacons",
+        "experience" => "Experience",
+        "unlockengram" => "Engrams",
+        "command" => "Commands",
+        _ => "Other",
+    }
+}
+
+fn push_grouped(groups: &mut Vec<CatalogGroup>, title: String, entry: CatalogEntry) {
+    match groups.iter_mut().find(|group| group.title == title) {
+        Some(group) => group.entries.push(entry),
+        None => groups.push(CatalogGroup { title, entries: vec![entry] }),
+    }
+}
+
+fn category_label(entry: &Value, categories: &HashMap<String, String>) -> String {
+    let category = entry_category(entry, categories);
+    // entry_category falls back to the entry's own Type when the catalog doesn't know it
+    let is_own_type = entry.get("Type").and_then(Value::as_str) == Some(category.as_str());
+    if category.trim().is_empty() || category == "-" || is_own_type { "Other".to_string() } else { category }
+}
+
+// Groups entries by type and, for items, catalog category, in config order
+pub fn build_catalog(config: &Value, catalog: &Value) -> Vec<CatalogSection> {
+    let names = display_names(catalog);
+    let categories = catalog_categories(catalog);
+    let mut sections = Vec::new();
+
+    if let Some(entries) = config.get("ShopItems").and_then(Value::as_object) {
+        let mut groups = Vec::new();
+        for (key, entry) in entries {
+            let entry_type = entry.get("Type").and_then(Value::as_str).unwrap_or_default();
+            let title = if entry_type == "item" {
+                format!("{}: {}", type_label(entry_type), category_label(entry, &categories))
+            } else {
+                type_label(entry_type).to_string()
+            };
+            push_grouped(&mut groups, title, shop_entry(key, entry, &names));
+        }
+        sections.push(CatalogSection { title: "Shop".to_string(), groups });
+    }
+
+    if let Some(kits) = config.get("Kits").and_then(Value::as_object) {
+        let entries = kits
+            .iter()
+            .map(|(key, kit)| CatalogEntry {
+                key: key.clone(),
+                name: text_field(kit, "Description").unwrap_or_else(|| key.clone()),
+                command: format!("/kit {}", key),
+                price: number_field(kit, "Price").filter(|price| price != "0").unwrap_or_default(),
+                details: contents(kit, &names),
+                description: text_field(kit, "Description").unwrap_or_default(),
+            })
+            .collect();
+        sections.push(CatalogSection {
+            title: "Kits".to_string(),
+            groups: vec![CatalogGroup { title: "Kits".to_string(), entries }],
+        });
+    }
+
+    if let Some(entries) = config.get("SellItems").and_then(Value::as_object) {
+        let mut groups = Vec::new();
+        for (key, entry) in entries {
+            let item = entry.get("Blueprint").and_then(Value::as_str).map(|blueprint| display_name(blueprint, &names));
+            let details = match (number_field(entry, "Amount"), &item) {
+                (Some(amount), Some(item)) => vec![format!("{}x {}", amount, item)],
+                _ => Vec::new(),
+            };
+            let sell_entry = CatalogEntry {
+                key: key.clone(),
+                name: text_field(entry, "Description").or(item).unwrap_or_else(|| key.clone()),
+                command: format!("/sell {}", key),
+                price: number_field(entry, "Price").unwrap_or_default(),
+                details,
+                description: text_field(entry, "Description").unwrap_or_default(),
+            };
+            push_grouped(&mut groups, format!("Sell: {}", category_label(entry, &categories)), sell_entry);
+        }
+        sections.push(CatalogSection { title: "Sell".to_string(), groups });
+    }
+
+    sections.retain(|section| section.groups.iter().any(|group| !group.entries.is_empty()));
+    sections
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '*' | '_' | '`' | '~' | '|' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+// Replaces {name} placeholders in one pass, leaving unknown ones as written
+pub fn fill_template(template: &str, values: &[(&str, &str)]) -> String {
+    let mut filled = String::with_capacity(template.len());
+    let mut pos = 0;
+
+    while let Some(found) = template[pos..].find('{') {
+        let start = pos + found;
+        filled.push_str(&template[pos..start]);
+        let value = template[start..].find('}').and_then(|len| {
+            let name = &template[start + 1..start + len];
+            values.iter().find(|(key, _)| *key == name).map(|(_, value)| (*value, len))
+        });
+        match value {
+            Some((value, len)) => {
+                filled.push_str(value);
+                pos = start + len + 1;
+            }
+            None => {
+                filled.push('{');
+                pos = start + 1;
+            }
+        }
+    }
+
+    filled.push_str(&template[pos..]);
+    filled
+}
+
+pub fn render_catalog(sections: &[CatalogSection], title: &str, format: CatalogFormat, templates: &CatalogTemplates) -> String {
+    let escape = |text: &str| match format {
+        CatalogFormat::Html => escape_html(text),
+        CatalogFormat::Markdown => escape_markdown(text),
+    };
+    // Commands sit in code spans in both default templates, so only HTML needs escaping
+    let escape_code = |text: &str| match format {
+        CatalogFormat::Html => escape_html(text),
+        CatalogFormat::Markdown => text.replace('`', "'"),
+    };
+
+    let mut body = String::new();
+    for section in sections {
+        let mut groups = String::new();
+        for group in &section.groups {
+            let mut entries = String::new();
+            for entry in &group.entries {
+                let rendered = fill_template(&templates.entry, &[
+                    ("name", &escape(&entry.name)),
+                    ("key", &escape(&entry.key)),
+                    ("command", &escape_code(&entry.command)),
+                    ("price", &escape(&entry.price)),
+                    ("details", &escape(&entry.details.join(", "))),
+                    ("description", &escape(&entry.description)),
+                ]);
+                // Empty fields shouldn't leave trailing spaces in Markdown
+                match format {
+                    CatalogFormat::Markdown => rendered.lines().for_each(|line| {
+                        entries.push_str(line.trim_end());
+                        entries.push('\n');
+                    }),
+                    CatalogFormat::Html => entries.push_str(&rendered),
+                }
+            }
+            groups.push_str(&fill_template(&templates.group, &[("title", &escape(&group.title)), ("content", &entries)]));
+        }
+        body.push_str(&fill_template(&templates.section, &[("title", &escape(&section.title)), ("content", &groups)]));
+    }
+
+    fill_template(&templates.page, &[("title", &escape(title)), ("content", &body)])
+}
+
+// Splits text into messages of at most `limit` characters, breaking between lines
+// where possible and inside a line only when it's longer than the limit on its own
+pub fn chunk_for_discord(text: &str, limit: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+
+    for line in text.lines() {
+        let mut line = line.to_string();
+        while line.chars().count() > limit {
+            if !current.is_empty() {
+                chunks.push(std::mem::take(&mut current));
+            }
+            let split = line.char_indices().nth(limit).map_or(line.len(), |(index, _)| index);
+            chunks.push(line[..split].to_string());
+            line = line[split..].to_string();
+        }
+
+        let needed = current.chars().count() + line.chars().count() + usize::from(!current.is_empty());
+        if needed > limit && !current.is_empty() {
+            chunks.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push('\n');
+        }
+        current.push_str(&line);
+    }
+
+    if !current.trim().is_empty() {
+        chunks.push(current);
+    }
+    chunks.retain(|chunk| !chunk.trim().is_empty());
+    chunks
+}
+
+pub fn export_catalog(config: &Value, catalog: &Value, title: &str, format: CatalogFormat, templates: &CatalogTemplates) -> RenderedCatalog {
+    let content = render_catalog(&build_catalog(config, catalog), title, format, templates);
+    let discord_chunks = match format {
+        CatalogFormat::Markdown => chunk_for_discord(&content, DISCORD_MESSAGE_LIMIT),
+        CatalogFormat::Html => Vec::new(),
+    };
+    RenderedCatalog { content, discord_chunks }
+}
@@ -0,0 +1,262 @@
+// src-tauri/src/command_safety.rs
+
+use serde::Serialize;
+use serde_json::Value;
+use crate::ark_data::IssueSeverity;
+
+// Tokens the plugin substitutes in command entries before running them
+pub const COMMAND_TOKENS: [&str; 5] = ["steamid", "eosid", "playerid", "tribeid", "playername"];
+
+// Console commands that wipe the server, move or empower players, or let them in unchecked.
+// A trailing '*' matches any command starting with the rest.
+pub const DEFAULT_DENY_LIST: [&str; 21] = [
+    "Destroy*",
+    "DestroyAll",
+    "KillAOE",
+    "Slomo",
+    "EnableCheats",
+    "SetCheatPlayer",
+    "God",
+    "Fly",
+    "Ghost",
+    "InfiniteStats",
+    "GiveCreativeMode",
+    "GiveAllStructure",
+    "GiveItem*",
+    "GiveExpToPlayer",
+    "Teleport*",
+    "SetPlayerPos",
+    "AllowPlayerToJoinNoCheck",
+    "BanPlayer",
+    "KickPlayer",
+    "DoExit",
+    "ClearPlayerInventory",
+];
+
+// Prefixes that run the rest of the line as an admin command
+const CHEAT_PREFIXES: [&str; 2] = ["admincheat", "cheat"];
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CommandProblem {
+    Empty,
+    UnbalancedBraces,
+    UnknownToken,
+    DeniedCommand,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CommandIssue {
+    pub key: String,
+    // Position in the entry's Items list
+    pub index: usize,
+    pub command: String,
+    pub problem: CommandProblem,
+    pub severity: IssueSeverity,
+    pub message: String,
+}
+
+pub fn default_deny_list() -> Vec<String> {
+    DEFAULT_DENY_LIST.iter().map(|pattern| pattern.to_string()).collect()
+}
+
+// The console command a line runs, with any cheat/admincheat prefix skipped
+pub fn command_word(line: &str) -> Option<&str> {
+    let mut words = line.split_whitespace();
+    let first = words.next()?;
+    if CHEAT_PREFIXES.iter().any(|prefix| prefix.eq_ignore_ascii_case(first)) {
+        words.next()
+    } else {
+        Some(first)
+    }
+}
+
+pub fn is_denied(word: &str, deny_list: &[String]) -> bool {
+    deny_list.iter().any(|pattern| {
+        let pattern = pattern.trim();
+        match pattern.strip_suffix('*') {
+            Some(prefix) => word.to_lowercase().starts_with(&prefix.to_lowercase()),
+            None => word.eq_ignore_ascii_case(pattern),
+        }
+    })
+}
+
+// {token} groups in a command, or Err with the offset of the first unbalanced brace
+pub fn command_tokens(command: &str) -> Result<Vec<String>, usize> {
+    let mut tokens = Vec::new();
+    let mut open: Option<usize> = None;
+
+    for (offset, c) in command.char_indices() {
+        match (c, open) {
+            ('{', None) => open = Some(offset),
+            ('{', Some(start)) => return Err(start),
+            ('}', Some(start)) => {
+                tokens.push(command[start + 1..offset].to_string());
+                open = None;
+            }
+            ('}', None) => return Err(offset),
+            _ => {}
+        }
+    }
+
+    match open {
+        Some(start) => Err(start),
+        None => Ok(tokens),
+    }
+}
+
+fn analyze_command(key: &str, index: usize, command: &str, deny_list: &[String], issues: &mut Vec<CommandIssue>) {
+    let mut push = |problem: CommandProblem, severity: IssueSeverity, message: String| {
+        issues.push(CommandIssue {
+            key: key.to_string(),
+            index,
+            command: command.to_string(),
+            problem,
+            severity,
+            message,
+        });
+    };
+
+    if command.trim().is_empty() {
+        push(CommandProblem::Empty, IssueSeverity::Error, "Command is empty".to_string());
+        return;
+    }
+
+    match command_tokens(command) {
+        Ok(tokens) => {
+            for token in tokens {
+                if !COMMAND_TOKENS.iter().any(|known| known.eq_ignore_ascii_case(token.trim())) {
+                    push(
+                        CommandProblem::UnknownToken,
+                        IssueSeverity::Error,
+                        format!("{{{}}} isn't replaced by the plugin, known tokens are {{{}}}", token, COMMAND_TOKENS.join("}, {")),
+                    );
+                }
+            }
+        }
+        Err(offset) => push(
+            CommandProblem::UnbalancedBraces,
+            IssueSeverity::Error,
+            format!("Unbalanced brace at position {}", offset),
+        ),
+    }
+
+    // The console runs '|'-separated commands one after another
+    for line in command.split('|') {
+        if let Some(word) = command_word(line) {
+            if is_denied(word, deny_list) {
+                push(
+                    CommandProblem::DeniedCommand,
+                    IssueSeverity::Error,
+                    format!("\"{}\" is on the command deny list and must be acknowledged before export", word),
+                );
+            }
+        }
+    }
+}
+
+pub fn analyze_commands(config: &Value, deny_list: &[String]) -> Vec<CommandIssue> {
+    let mut issues = Vec::new();
+    let entries = match config.get("ShopItems").and_then(Value::as_object) {
+        Some(entries) => entries,
+        None => return issues,
+    };
+
+    for (key, entry) in entries {
+        if entry.get("Type").and_then(Value::as_str) != Some("command") {
+            continue;
+        }
+        for (index, item) in entry.get("Items").and_then(Value::as_array).into_iter().flatten().enumerate() {
+            let command = item.get("Command").and_then(Value::as_str).unwrap_or_default();
+            analyze_command(key, index, command, deny_list, &mut issues);
+        }
+    }
+
+    issues
+}
+
+// Entry keys holding denied commands, in config order
+pub fn denied_entries(config: &Value, deny_list: &[String]) -> Vec<String> {
+    let mut keys: Vec<String> = Vec::new();
+    for issue in analyze_commands(config, deny_list) {
+        if issue.problem == CommandProblem::DeniedCommand && !keys.contains(&issue.key) {
+            keys.push(issue.key);
+        }
+    }
+    keys
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn command_word_skips_cheat_prefixes() {
+        let cases = [
+            ("GiveExpToPlayer 123 1000 0 0", Some("GiveExpToPlayer")),
+            ("cheat GiveExpToPlayer 123 1000 0 0", Some("GiveExpToPlayer")),
+            ("  AdminCheat   SetPlayerPos 0 0 0", Some("SetPlayerPos")),
+            ("ADMINCHEAT fly", Some("fly")),
+            ("cheat", None),
+            ("   ", None),
+            ("cheats Fly", Some("cheats")),
+        ];
+        for (line, word) in cases {
+            assert_eq!(command_word(line), word, "{:?}", line);
+        }
+    }
+
+    #[test]
+    fn deny_list_matches_exact_words_and_wildcards() {
+        let deny = default_deny_list();
+        for word in ["Fly", "fly", "GHOST", "TeleportToPlayer", "teleport", "GiveItemToPlayer", "GiveItemNum", "DestroyAll", "DestroyWildDinos", "AllowPlayerToJoinNoCheck", "SetPlayerPos"] {
+            assert!(is_denied(word, &deny), "{} should be denied", word);
+        }
+        for word in ["SaveWorld", "Flying", "GodMode", "ServerChat", "GiveExpToPlayers", "AddExperience"] {
+            assert!(!is_denied(word, &deny), "{} shouldn't be denied", word);
+        }
+        // Patterns are trimmed and a bare * denies everything
+        assert!(is_denied("Slomo", &[" slomo ".to_string()]));
+        assert!(is_denied("Anything", &["*".to_string()]));
+        assert!(!is_denied("Anything", &[]));
+    }
+
+    #[test]
+    fn command_tokens_reports_the_first_unbalanced_brace() {
+        assert_eq!(command_tokens("GiveExpToPlayer {playerid} 100 0 0"), Ok(vec!["playerid".to_string()]));
+        assert_eq!(command_tokens("ServerChat {playername} joined {tribeid}"), Ok(vec!["playername".to_string(), "tribeid".to_string()]));
+        assert_eq!(command_tokens("no tokens"), Ok(Vec::new()));
+        assert_eq!(command_tokens("GiveExpToPlayer {playerid 100"), Err(16));
+        assert_eq!(command_tokens("GiveExpToPlayer playerid} 100"), Err(24));
+        assert_eq!(command_tokens("{steamid {eosid}}"), Err(0));
+        // Offsets are bytes, so they line up with the command string
+        assert_eq!(command_tokens("ServerChat é {"), Err(14));
+    }
+
+    #[test]
+    fn analyzes_every_command_line() {
+        let config = json!({
+            "ShopItems": {
+                "announce": { "Type": "command", "Items": [{ "Command": "ServerChat {playername} bought a kit" }] },
+                "xp": { "Type": "command", "Items": [{ "Command": "cheat GiveExpToPlayer {playerid} 100 0 0" }] },
+                "chained": { "Type": "command", "Items": [{ "Command": "ServerChat hi | admincheat TeleportToPlayer {playerid}" }, { "Command": " " }] },
+                "typo": { "Type": "command", "Items": [{ "Command": "ServerChat {player}" }] },
+                "item": { "Type": "item", "Items": [{ "Command": "DoExit" }] }
+            }
+        });
+        let deny = default_deny_list();
+        let analyzed = analyze_commands(&config, &deny);
+        let issues: Vec<(&str, usize, CommandProblem)> = analyzed
+            .iter()
+            .map(|issue| (issue.key.as_str(), issue.index, issue.problem))
+            .collect();
+        assert_eq!(issues, vec![
+            ("xp", 0, CommandProblem::DeniedCommand),
+            ("chained", 0, CommandProblem::DeniedCommand),
+            ("chained", 1, CommandProblem::Empty),
+            ("typo", 0, CommandProblem::UnknownToken),
+        ]);
+        assert_eq!(denied_entries(&config, &deny), vec!["xp", "chained"]);
+    }
+}
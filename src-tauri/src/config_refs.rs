@@ -0,0 +1,109 @@
+// src-tauri/src/config_refs.rs
+
+use serde::Serialize;
+use serde_json::Value;
+
+// Sections whose entries can reference catalog blueprints
+pub const ENTRY_SECTIONS: [&str; 3] = ["ShopItems", "Kits", "SellItems"];
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct CatalogRef {
+    // JSON pointer to the field (or map key) holding the reference
+    pub pointer: String,
+    pub value: String,
+    // True when the reference is an object key, as in GachaResources
+    pub is_key: bool,
+}
+
+pub fn escape_pointer_segment(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+pub fn pointer(segments: &[&str]) -> String {
+    segments
+        .iter()
+        .map(|segment| format!("/{}", escape_pointer_segment(segment)))
+        .collect()
+}
+
+fn is_blueprint_field(field: &str) -> bool {
+    field.ends_with("Blueprint") && field != "ForceBlueprint"
+}
+
+fn collect_blueprints(value: &Value, path: &str, refs: &mut Vec<CatalogRef>) {
+    match value {
+        Value::Object(fields) => {
+            for (field, child) in fields {
+                let child_path = format!("{}/{}", path, escape_pointer_segment(field));
+                match child {
+                    Value::String(blueprint) if is_blueprint_field(field) => {
+                        if !blueprint.trim().is_empty() {
+                            refs.push(CatalogRef {
+                                pointer: child_path,
+                                value: blueprint.clone(),
+                                is_key: false,
+                            });
+                        }
+                    }
+                    Value::Object(resources) if field == "GachaResources" => {
+                        for resource in resources.keys() {
+                            refs.push(CatalogRef {
+                                pointer: format!("{}/{}", child_path, escape_pointer_segment(resource)),
+                                value: resource.clone(),
+                                is_key: true,
+                            });
+                        }
+                    }
+                    _ => collect_blueprints(child, &child_path, refs),
+                }
+            }
+        }
+        Value::Array(values) => {
+            for (index, child) in values.iter().enumerate() {
+                collect_blueprints(child, &format!("{}/{}", path, index), refs);
+            }
+        }
+        _ => {}
+    }
+}
+
+pub fn blueprint_refs(config: &Value) -> Vec<CatalogRef> {
+    let mut refs = Vec::new();
+
+    if let Some(cryo) = config.pointer("/General/CryoItemPath").and_then(Value::as_str) {
+        if !cryo.trim().is_empty() {
+            refs.push(CatalogRef {
+                pointer: "/General/CryoItemPath".to_string(),
+                value: cryo.to_string(),
+                is_key: false,
+            });
+        }
+    }
+
+    for section in ENTRY_SECTIONS.iter() {
+        if let Some(entries) = config.get(*section) {
+            collect_blueprints(entries, &pointer(&[section]), &mut refs);
+        }
+    }
+
+    refs
+}
+
+pub fn beacon_class_refs(config: &Value) -> Vec<CatalogRef> {
+    let mut refs = Vec::new();
+    if let Some(entries) = config.get("ShopItems").and_then(Value::as_object) {
+        for (key, entry) in entries {
+            if entry.get("Type").and_then(Value::as_str) != Some("beacon") {
+                continue;
+            }
+            if let Some(class_name) = entry.get("ClassName").and_then(Value::as_str) {
+                refs.push(CatalogRef {
+                    pointer: pointer(&["ShopItems", key, "ClassName"]),
+                    value: class_name.to_string(),
+                    is_key: false,
+                });
+            }
+        }
+    }
+    refs
+}
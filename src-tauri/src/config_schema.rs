@@ -0,0 +1,331 @@
+// src-tauri/src/config_schema.rs
+
+use serde_json::{json, Map, Value};
+use crate::message_schema::{default_config, message_schema};
+use crate::shop_validation::{type_fields, COMMON_FIELDS, ENTRY_TYPES, GENDERS, MAX_DINO_LEVEL, MAX_QUALITY, STRYDER_ATTACHMENTS};
+
+const SCHEMA_DRAFT: &str = "http://json-schema.org/draft-07/schema#";
+
+// Blueprint and class name lists from the catalog, offered as completions
+#[derive(Default)]
+pub struct CatalogEnums {
+    pub items: Vec<String>,
+    pub dinos: Vec<String>,
+    pub engrams: Vec<String>,
+    pub beacons: Vec<String>,
+}
+
+impl CatalogEnums {
+    pub fn from_catalog(catalog: &Value) -> Self {
+        let collect = |section: &str, field: &str| -> Vec<String> {
+            let mut values: Vec<String> = catalog
+                .get(section)
+                .and_then(Value::as_object)
+                .map(|entries| {
+                    entries
+                        .values()
+                        .filter_map(|entry| entry.get(field).and_then(Value::as_str))
+                        .map(|value| value.trim().to_string())
+                        .filter(|value| !value.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default();
+            values.sort();
+            values.dedup();
+            values
+        };
+        CatalogEnums {
+            items: collect("Items", "Blueprint"),
+            dinos: collect("Dinos", "Blueprint"),
+            engrams: collect("Engrams", "Blueprint"),
+            beacons: collect("Beacons", "ClassName"),
+        }
+    }
+}
+
+// Catalog values either restrict the field (strict) or are only suggested,
+// matching how validation treats dinos/engrams versus modded items
+fn catalog_string(values: Option<&Vec<String>>, strict: bool) -> Value {
+    match values.filter(|values| !values.is_empty()) {
+        Some(values) if strict => json!({ "type": "string", "enum": values }),
+        Some(values) => json!({ "type": "string", "anyOf": [{ "enum": values }, { "type": "string" }] }),
+        None => json!({ "type": "string" }),
+    }
+}
+
+// ArkShop reads some values case-insensitively; JSON Schema has no such enum, so spell it out
+fn any_case(values: &[&str]) -> Value {
+    let alternatives: Vec<String> = values
+        .iter()
+        .map(|value| {
+            value
+                .chars()
+                .map(|c| if c.is_ascii_alphabetic() { format!("[{}{}]", c.to_ascii_lowercase(), c.to_ascii_uppercase()) } else { c.to_string() })
+                .collect()
+        })
+        .collect();
+    json!({ "type": "string", "pattern": format!("^({})$", alternatives.join("|")), "examples": values })
+}
+
+fn integer(minimum: i64) -> Value {
+    json!({ "type": "integer", "minimum": minimum })
+}
+
+fn object(properties: Map<String, Value>, required: &[&str]) -> Value {
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
+
+fn common_fields() -> Map<String, Value> {
+    let mut properties = Map::new();
+    for field in COMMON_FIELDS.iter() {
+        let schema = match *field {
+            "Type" => json!({ "type": "string", "enum": ENTRY_TYPES }),
+            "Price" => json!({ "type": "number", "minimum": 0 }),
+            "MinLevel" | "MaxLevel" => integer(1),
+            "Permissions" => json!({ "type": "string", "description": "Comma separated permission groups" }),
+            _ => json!({ "type": "string" }),
+        };
+        properties.insert(field.to_string(), schema);
+    }
+    properties
+}
+
+fn line_schema(entry_type: &str, enums: Option<&CatalogEnums>) -> Value {
+    let mut properties = Map::new();
+    let required: &[&str] = match entry_type {
+        "item" => {
+            properties.insert("Amount".to_string(), integer(1));
+            properties.insert("Quality".to_string(), json!({ "type": "number", "minimum": 0, "maximum": MAX_QUALITY }));
+            properties.insert("ForceBlueprint".to_string(), json!({ "type": "boolean" }));
+            properties.insert("Blueprint".to_string(), catalog_string(enums.map(|enums| &enums.items), false));
+            &["Blueprint"]
+        }
+        "unlockengram" => {
+            properties.insert("Blueprint".to_string(), catalog_string(enums.map(|enums| &enums.engrams), true));
+            &["Blueprint"]
+        }
+        "command" => {
+            properties.insert("Command".to_string(), json!({ "type": "string", "minLength": 1 }));
+            properties.insert("DisplayAs".to_string(), json!({ "type": "string" }));
+            properties.insert("ExecuteAsAdmin".to_string(), json!({ "type": "boolean" }));
+            &["Command"]
+        }
+        _ => &[],
+    };
+    object(properties, required)
+}
+
+fn dino_line_schema(enums: Option<&CatalogEnums>) -> Value {
+    let mut properties = Map::new();
+    properties.insert("Level".to_string(), json!({ "type": "integer", "minimum": 1, "maximum": MAX_DINO_LEVEL }));
+    properties.insert("Blueprint".to_string(), catalog_string(enums.map(|enums| &enums.dinos), true));
+    properties.insert("Neutered".to_string(), json!({ "type": "boolean" }));
+    properties.insert("Gender".to_string(), any_case(&GENDERS));
+    properties.insert("SaddleBlueprint".to_string(), catalog_string(enums.map(|enums| &enums.items), false));
+    properties.insert("PreventCryo".to_string(), json!({ "type": "boolean" }));
+    object(properties, &["Level", "Blueprint"])
+}
+
+// The schema for one ShopItems Type, built from the same field lists validation uses
+fn shop_variant(entry_type: &str, enums: Option<&CatalogEnums>) -> Value {
+    let (required, optional) = type_fields(entry_type);
+    let mut properties = common_fields();
+    properties.insert("Type".to_string(), json!({ "const": entry_type }));
+
+    for field in required.iter().chain(optional.iter()) {
+        let schema = match *field {
+            "Items" => json!({ "type": "array", "minItems": 1, "items": line_schema(entry_type, enums) }),
+            "Blueprint" => catalog_string(enums.map(|enums| &enums.dinos), true),
+            "SaddleBlueprint" => catalog_string(enums.map(|enums| &enums.items), false),
+            "ClassName" => catalog_string(enums.map(|enums| &enums.beacons), false),
+            "Level" => json!({ "type": "integer", "minimum": 1, "maximum": MAX_DINO_LEVEL }),
+            "Amount" => integer(1),
+            "Gender" => any_case(&GENDERS),
+            "StryderHead" | "StryderChest" => json!({
+                "type": "integer",
+                "minimum": STRYDER_ATTACHMENTS.start(),
+                "maximum": STRYDER_ATTACHMENTS.end(),
+            }),
+            "GachaResources" => json!({ "type": "object", "additionalProperties": { "type": "number" } }),
+            _ => json!({ "type": "boolean" }),
+        };
+        properties.insert(field.to_string(), schema);
+    }
+    // The editor gives every new entry an empty Items list
+    properties.entry("Items".to_string()).or_insert_with(|| json!({ "type": "array", "maxItems": 0 }));
+
+    let mut required_fields = vec!["Type"];
+    required_fields.extend_from_slice(required);
+    object(properties, &required_fields)
+}
+
+fn shop_items_schema(enums: Option<&CatalogEnums>) -> Value {
+    let variants: Vec<Value> = ENTRY_TYPES
+        .iter()
+        .map(|entry_type| {
+            json!({
+                "if": { "properties": { "Type": { "const": entry_type } }, "required": ["Type"] },
+                "then": shop_variant(entry_type, enums),
+            })
+        })
+        .collect();
+
+    json!({
+        "type": "object",
+        "additionalProperties": {
+            "type": "object",
+            "required": ["Type"],
+            "properties": { "Type": { "type": "string", "enum": ENTRY_TYPES } },
+            "allOf": variants,
+        },
+    })
+}
+
+fn kits_schema(enums: Option<&CatalogEnums>) -> Value {
+    let mut properties = common_fields();
+    properties.remove("Type");
+    properties.remove("Title");
+    properties.insert("DefaultAmount".to_string(), integer(0));
+    properties.insert("OnlyFromSpawn".to_string(), json!({ "type": "boolean" }));
+    properties.insert("Items".to_string(), json!({ "type": "array", "items": line_schema("item", enums) }));
+    properties.insert("Dinos".to_string(), json!({ "type": "array", "items": dino_line_schema(enums) }));
+    json!({ "type": "object", "additionalProperties": object(properties, &[]) })
+}
+
+fn sell_items_schema(enums: Option<&CatalogEnums>) -> Value {
+    let mut properties = Map::new();
+    properties.insert("Type".to_string(), json!({ "const": "item" }));
+    properties.insert("Description".to_string(), json!({ "type": "string" }));
+    properties.insert("Price".to_string(), json!({ "type": "number", "minimum": 0 }));
+    properties.insert("Amount".to_string(), integer(1));
+    properties.insert("Blueprint".to_string(), catalog_string(enums.map(|enums| &enums.items), false));
+    json!({ "type": "object", "additionalProperties": object(properties, &["Type", "Price", "Amount", "Blueprint"]) })
+}
+
+fn messages_schema() -> Value {
+    let properties: Map<String, Value> = message_schema()
+        .into_values()
+        .map(|message| (message.key, json!({ "type": "string", "default": message.default_text })))
+        .collect();
+    // Unknown keys are ignored by the plugin, so they aren't rejected here either
+    json!({ "type": "object", "properties": properties, "additionalProperties": { "type": "string" } })
+}
+
+// Settings sections are described by the stock config's own values
+fn infer_schema(value: &Value) -> Value {
+    match value {
+        Value::Bool(_) => json!({ "type": "boolean", "default": value }),
+        Value::Number(number) if number.is_f64() => json!({ "type": "number", "default": value }),
+        Value::Number(_) => json!({ "type": "integer", "default": value }),
+        Value::String(_) => json!({ "type": "string", "default": value }),
+        Value::Object(fields) => {
+            let properties: Map<String, Value> = fields.iter().map(|(key, child)| (key.clone(), infer_schema(child))).collect();
+            json!({ "type": "object", "properties": properties })
+        }
+        Value::Array(_) => json!({ "type": "array" }),
+        Value::Null => json!({}),
+    }
+}
+
+pub fn config_schema(enums: Option<&CatalogEnums>) -> Value {
+    let defaults = default_config();
+    let mut properties = Map::new();
+
+    for (section, value) in defaults.as_object().into_iter().flatten() {
+        let schema = match section.as_str() {
+            "ShopItems" => shop_items_schema(enums),
+            "Kits" => kits_schema(enums),
+            "SellItems" => sell_items_schema(enums),
+            "Messages" => messages_schema(),
+            _ => infer_schema(value),
+        };
+        properties.insert(section.clone(), schema);
+    }
+
+    let mut schema = json!({
+        "$schema": SCHEMA_DRAFT,
+        "title": "ArkShop config.json",
+        "type": "object",
+        "properties": properties,
+    });
+
+    // TimedPointsReward groups are keyed by permission group name
+    if let Some(groups) = schema.pointer_mut("/properties/General/properties/TimedPointsReward/properties/Groups") {
+        *groups = json!({
+            "type": "object",
+            "additionalProperties": { "type": "object", "properties": { "Amount": integer(0) } },
+        });
+    }
+
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use jsonschema::JSONSchema;
+
+    fn assert_valid(schema: &Value, config: &Value) {
+        let compiled = JSONSchema::compile(schema).expect("schema compiles");
+        if let Err(errors) = compiled.validate(config) {
+            let messages: Vec<String> = errors.map(|error| format!("{}: {}", error.instance_path, error)).collect();
+            panic!("config doesn't match the schema:\n{}", messages.join("\n"));
+        };
+    }
+
+    // Entries as the editor creates them (ShopItemsSettings, KitsSettings, SellItemsSettings,
+    // DinoShopEntry), with the fields the user has to fill in set
+    fn editor_entries() -> Value {
+        let mut config = default_config();
+        config["ShopItems"] = json!({
+            "item": {
+                "Type": "item", "Description": "", "Price": 0,
+                "Items": [{ "Quality": 0, "ForceBlueprint": false, "Amount": 1, "Blueprint": "Blueprint'/Game/Item.Item'" }]
+            },
+            "dino": {
+                "Type": "dino", "Description": "", "Price": 0, "Items": [],
+                "Level": 150, "Blueprint": "Blueprint'/Game/Dino.Dino'", "Neutered": false, "PreventCryo": false,
+                "Gender": "Male", "SaddleBlueprint": ""
+            },
+            "random": { "Type": "dino", "Price": 0, "Items": [], "Level": 1, "Blueprint": "Blueprint'/Game/Dino.Dino'", "Gender": "random" },
+            "beacon": { "Type": "beacon", "Description": "", "Price": 0, "Items": [], "ClassName": "SupplyCrate_C" },
+            "experience": { "Type": "experience", "Description": "", "Price": 0, "Items": [], "Amount": 100, "GiveToDino": false },
+            "engram": { "Type": "unlockengram", "Description": "", "Price": 0, "Items": [{ "Blueprint": "Blueprint'/Game/Engram.Engram'" }] },
+            "command": { "Type": "command", "Description": "", "Price": 0, "Items": [{ "Command": "cheat god", "DisplayAs": "" }] }
+        });
+        config["Kits"] = json!({
+            "starter": {
+                "DefaultAmount": 1, "Description": "", "Permissions": "", "Price": 0, "MinLevel": 1, "MaxLevel": 2, "OnlyFromSpawn": false,
+                "Items": [{ "Quality": 0, "ForceBlueprint": false, "Amount": 1, "Blueprint": "Blueprint'/Game/Item.Item'" }],
+                "Dinos": [{ "Level": 1, "Blueprint": "Blueprint'/Game/Dino.Dino'", "Gender": "FEMALE" }]
+            }
+        });
+        config["SellItems"] = json!({
+            "stone": { "Type": "item", "Description": "", "Price": 0, "Amount": 1, "Blueprint": "Blueprint'/Game/Stone.Stone'" }
+        });
+        config
+    }
+
+    #[test]
+    fn bundled_config_matches_the_schema() {
+        assert_valid(&config_schema(None), &default_config());
+    }
+
+    #[test]
+    fn editor_entries_match_the_schema() {
+        assert_valid(&config_schema(None), &editor_entries());
+    }
+
+    #[test]
+    fn gender_accepts_any_case_but_not_other_values() {
+        let schema = JSONSchema::compile(&config_schema(None)).unwrap();
+        let mut config = editor_entries();
+        config["ShopItems"]["dino"]["Gender"] = json!("Both");
+        assert!(!schema.is_valid(&config));
+    }
+}
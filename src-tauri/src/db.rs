@@ -4,7 +4,8 @@ use rusqlite::{Connection, Result, Error, params, OptionalExtension};
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use tauri::AppHandle;
-use chrono::NaiveDate;
+use chrono::{DateTime, NaiveDate, Utc};
+use crate::migrations::migrate;
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Settings {
@@ -22,6 +23,43 @@ pub struct SavedConfig {
     pub custom_export_paths: Option<Vec<String>>,
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+pub struct MessagePack {
+    pub id: Option<i64>,
+    pub name: String,
+    pub messages: String,
+    pub is_default: bool,
+}
+
+// The map an export path's server runs, used to check beacons on export
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ExportPathMap {
+    pub path: String,
+    pub map: String,
+    // Leave out beacons the map doesn't have instead of exporting them anyway
+    #[serde(default)]
+    pub drop_incompatible: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct BackupSchedule {
+    pub directory: String,
+    pub interval_hours: i64,
+    // Number of scheduled backups kept in directory
+    pub retention: i64,
+    pub last_backup: Option<DateTime<Utc>>,
+}
+
+// How the key for encrypted config secrets is kept; the key itself is never stored here
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SecretKeyInfo {
+    pub source: String,
+    // Argon2 salt for passphrase keys
+    pub salt: Option<String>,
+    // A known value sealed with the key, used to check a passphrase or keyring key
+    pub verifier: String,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct LicenseInfo {
     pub license_key: String,
@@ -29,10 +67,83 @@ pub struct LicenseInfo {
     pub hwid: String,
 }
 
+const DATABASE_FILE: &str = "settings.db";
+// Kept outside the database since it decides which database to open
+const LOCATION_FILE: &str = "database_location.json";
+
+#[derive(Serialize, Deserialize, Debug)]
+struct DatabaseOverride {
+    // Empty when the override was cleared, so `previous` is still known
+    path: String,
+    // The database in use when the override was saved, copied from on the next launch
+    #[serde(default)]
+    previous: Option<String>,
+}
+
+// Where releases before the move to app data kept the database
+fn legacy_database_path(handle: &AppHandle) -> Option<PathBuf> {
+    handle.path_resolver().resolve_resource(DATABASE_FILE)
+}
+
+pub fn default_database_path(handle: &AppHandle) -> Option<PathBuf> {
+    handle.path_resolver().app_data_dir().map(|dir| dir.join(DATABASE_FILE))
+}
+
+fn location_file(handle: &AppHandle) -> Option<PathBuf> {
+    handle.path_resolver().app_config_dir().map(|dir| dir.join(LOCATION_FILE))
+}
+
+fn load_location(handle: &AppHandle) -> Option<DatabaseOverride> {
+    let raw = std::fs::read_to_string(location_file(handle)?).ok()?;
+    serde_json::from_str(&raw).ok()
+}
+
+pub fn load_database_override(handle: &AppHandle) -> Option<PathBuf> {
+    Some(PathBuf::from(load_location(handle)?.path)).filter(|path| !path.as_os_str().is_empty())
+}
+
+fn load_previous_database_path(handle: &AppHandle) -> Option<PathBuf> {
+    load_location(handle)?.previous.map(PathBuf::from)
+}
+
+pub fn save_database_override(handle: &AppHandle, path: Option<&Path>, previous: &Path) -> std::io::Result<()> {
+    let file = location_file(handle).ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "Failed to resolve app config directory"))?;
+    if let Some(parent) = file.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let location = DatabaseOverride {
+        path: path.map(|path| path.to_string_lossy().into_owned()).unwrap_or_default(),
+        previous: Some(previous.to_string_lossy().into_owned()),
+    };
+    std::fs::write(&file, serde_json::to_string_pretty(&location)?)
+}
+
+// The override path (e.g. a shared network drive) wins over the per-user app data directory
 pub fn get_database_path(handle: &AppHandle) -> PathBuf {
-    handle.path_resolver()
-        .resolve_resource("settings.db")
-        .expect("failed to resolve resource")
+    load_database_override(handle)
+        .or_else(|| default_database_path(handle))
+        .or_else(|| legacy_database_path(handle))
+        .expect("failed to resolve database path")
+}
+
+// Copies the database from the one in use when the location was changed, falling back to the
+// app data or install directory, the first time a new location is used; the old file is
+// left in place. Returns where it was copied from.
+pub fn migrate_database_location(handle: &AppHandle, db_path: &Path) -> std::io::Result<Option<PathBuf>> {
+    if db_path.exists() {
+        return Ok(None);
+    }
+    let candidates = [load_previous_database_path(handle), default_database_path(handle), legacy_database_path(handle)];
+    for candidate in candidates.iter().flatten() {
+        if candidate.as_path() != db_path && candidate.exists() {
+            if let Some(parent) = db_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::copy(candidate, db_path)?;
+            return Ok(Some(candidate.clone()));
+        }
+    }
+    Ok(None)
 }
 
 pub fn ensure_database_exists(db_path: &Path) -> Result<()> {
@@ -44,39 +155,12 @@ pub fn ensure_database_exists(db_path: &Path) -> Result<()> {
     Ok(())
 }
 
-pub fn initialize_db(db_path: &Path) -> Result<Connection> {
+// Also returns the backup taken when the schema had to be migrated
+pub fn initialize_db(db_path: &Path) -> Result<(Connection, Option<PathBuf>)> {
     ensure_database_exists(db_path)?;
-    let conn = Connection::open(db_path)?;
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS settings (
-            id INTEGER PRIMARY KEY,
-            output_path TEXT NOT NULL,
-            auto_save_enabled BOOLEAN NOT NULL DEFAULT 0,
-            auto_save_interval INTEGER NOT NULL DEFAULT 5,
-            show_tooltips BOOLEAN NOT NULL DEFAULT 1
-        )",
-        [],
-    )?;
-    
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS saved_configs (
-            id INTEGER PRIMARY KEY,
-            name TEXT NOT NULL UNIQUE,
-            config TEXT NOT NULL,
-            custom_export_paths TEXT
-        )",
-        [],
-    )?;
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS license_info (
-            id INTEGER PRIMARY KEY,
-            license_key TEXT NOT NULL,
-            expiration_date TEXT NOT NULL,
-            hwid TEXT NOT NULL
-        )",
-        [],
-    )?;
-    Ok(conn)
+    let mut conn = Connection::open(db_path)?;
+    let backup = migrate(&mut conn, db_path)?;
+    Ok((conn, backup))
 }
 
 pub fn save_settings(conn: &Connection, settings: &Settings) -> Result<()> {
@@ -151,6 +235,7 @@ pub fn load_configs(conn: &Connection) -> Result<Vec<SavedConfig>> {
 
 pub fn delete_config(conn: &Connection, id: i64) -> Result<()> {
     conn.execute("DELETE FROM saved_configs WHERE id = ?1", params![id])?;
+    conn.execute("DELETE FROM config_message_packs WHERE config_id = ?1", params![id])?;
     Ok(())
 }
 
@@ -245,4 +330,264 @@ pub fn load_config_by_id(conn: &Connection, id: i64) -> Result<Option<SavedConfi
             })
         }
     ).optional()
-}
\ No newline at end of file
+}
+
+fn message_pack_from_row(row: &rusqlite::Row) -> Result<MessagePack> {
+    Ok(MessagePack {
+        id: Some(row.get(0)?),
+        name: row.get(1)?,
+        messages: row.get(2)?,
+        is_default: row.get(3)?,
+    })
+}
+
+pub fn save_message_pack(conn: &Connection, pack: &MessagePack) -> Result<i64> {
+    conn.execute(
+        "INSERT INTO message_packs (name, messages, is_default) VALUES (?1, ?2, ?3)",
+        params![pack.name, pack.messages, pack.is_default],
+    )?;
+    let id = conn.last_insert_rowid();
+    if pack.is_default {
+        set_default_message_pack(conn, id)?;
+    }
+    Ok(id)
+}
+
+pub fn update_message_pack(conn: &Connection, id: i64, name: &str, messages: &str) -> Result<()> {
+    conn.execute(
+        "UPDATE message_packs SET name = ?1, messages = ?2 WHERE id = ?3",
+        params![name, messages, id],
+    )?;
+    Ok(())
+}
+
+pub fn load_message_packs(conn: &Connection) -> Result<Vec<MessagePack>> {
+    let mut stmt = conn.prepare("SELECT id, name, messages, is_default FROM message_packs ORDER BY name")?;
+    let pack_iter = stmt.query_map([], message_pack_from_row)?;
+
+    let mut packs = Vec::new();
+    for pack in pack_iter {
+        packs.push(pack?);
+    }
+    Ok(packs)
+}
+
+pub fn load_message_pack_by_id(conn: &Connection, id: i64) -> Result<Option<MessagePack>> {
+    conn.query_row(
+        "SELECT id, name, messages, is_default FROM message_packs WHERE id = ?1",
+        params![id],
+        message_pack_from_row,
+    ).optional()
+}
+
+pub fn load_default_message_pack(conn: &Connection) -> Result<Option<MessagePack>> {
+    conn.query_row(
+        "SELECT id, name, messages, is_default FROM message_packs WHERE is_default = 1",
+        [],
+        message_pack_from_row,
+    ).optional()
+}
+
+pub fn message_pack_name_exists(conn: &Connection, name: &str) -> Result<bool> {
+    let count: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM message_packs WHERE name = ?1",
+        params![name],
+        |row| row.get(0),
+    )?;
+    Ok(count > 0)
+}
+
+pub fn set_default_message_pack(conn: &Connection, id: i64) -> Result<()> {
+    conn.execute("UPDATE message_packs SET is_default = (id = ?1)", params![id])?;
+    Ok(())
+}
+
+pub fn delete_message_pack(conn: &Connection, id: i64) -> Result<()> {
+    conn.execute("DELETE FROM message_packs WHERE id = ?1", params![id])?;
+    conn.execute("DELETE FROM config_message_packs WHERE pack_id = ?1", params![id])?;
+    conn.execute("DELETE FROM export_path_message_packs WHERE pack_id = ?1", params![id])?;
+    Ok(())
+}
+
+pub fn set_config_message_pack(conn: &Connection, config_id: i64, pack_id: Option<i64>) -> Result<()> {
+    match pack_id {
+        Some(pack_id) => conn.execute(
+            "INSERT OR REPLACE INTO config_message_packs (config_id, pack_id) VALUES (?1, ?2)",
+            params![config_id, pack_id],
+        )?,
+        None => conn.execute("DELETE FROM config_message_packs WHERE config_id = ?1", params![config_id])?,
+    };
+    Ok(())
+}
+
+pub fn load_config_message_pack(conn: &Connection, config_id: i64) -> Result<Option<i64>> {
+    conn.query_row(
+        "SELECT pack_id FROM config_message_packs WHERE config_id = ?1",
+        params![config_id],
+        |row| row.get(0),
+    ).optional()
+}
+
+pub fn set_export_path_message_pack(conn: &Connection, path: &str, pack_id: Option<i64>) -> Result<()> {
+    match pack_id {
+        Some(pack_id) => conn.execute(
+            "INSERT OR REPLACE INTO export_path_message_packs (path, pack_id) VALUES (?1, ?2)",
+            params![path, pack_id],
+        )?,
+        None => conn.execute("DELETE FROM export_path_message_packs WHERE path = ?1", params![path])?,
+    };
+    Ok(())
+}
+
+pub fn load_export_path_message_pack(conn: &Connection, path: &str) -> Result<Option<i64>> {
+    conn.query_row(
+        "SELECT pack_id FROM export_path_message_packs WHERE path = ?1",
+        params![path],
+        |row| row.get(0),
+    ).optional()
+}
+
+pub fn save_permissions_source(conn: &Connection, source: Option<&str>) -> Result<()> {
+    match source {
+        Some(source) => conn.execute(
+            "INSERT OR REPLACE INTO permissions_source (id, source) VALUES (1, ?1)",
+            params![source],
+        )?,
+        None => conn.execute("DELETE FROM permissions_source WHERE id = 1", [])?,
+    };
+    Ok(())
+}
+
+pub fn load_permissions_source(conn: &Connection) -> Result<Option<String>> {
+    conn.query_row(
+        "SELECT source FROM permissions_source WHERE id = 1",
+        [],
+        |row| row.get(0),
+    ).optional()
+}
+
+pub fn set_export_path_map(conn: &Connection, path: &str, map: Option<&str>, drop_incompatible: bool) -> Result<()> {
+    match map {
+        Some(map) => conn.execute(
+            "INSERT OR REPLACE INTO export_path_maps (path, map, drop_incompatible) VALUES (?1, ?2, ?3)",
+            params![path, map, drop_incompatible],
+        )?,
+        None => conn.execute("DELETE FROM export_path_maps WHERE path = ?1", params![path])?,
+    };
+    Ok(())
+}
+
+pub fn load_export_path_map(conn: &Connection, path: &str) -> Result<Option<ExportPathMap>> {
+    conn.query_row(
+        "SELECT path, map, drop_incompatible FROM export_path_maps WHERE path = ?1",
+        params![path],
+        |row| Ok(ExportPathMap { path: row.get(0)?, map: row.get(1)?, drop_incompatible: row.get(2)? }),
+    ).optional()
+}
+
+pub fn save_command_deny_list(conn: &Connection, patterns: Option<&str>) -> Result<()> {
+    match patterns {
+        Some(patterns) => conn.execute(
+            "INSERT OR REPLACE INTO command_deny_list (id, patterns) VALUES (1, ?1)",
+            params![patterns],
+        )?,
+        None => conn.execute("DELETE FROM command_deny_list WHERE id = 1", [])?,
+    };
+    Ok(())
+}
+
+pub fn load_command_deny_list(conn: &Connection) -> Result<Option<String>> {
+    conn.query_row(
+        "SELECT patterns FROM command_deny_list WHERE id = 1",
+        [],
+        |row| row.get(0),
+    ).optional()
+}
+
+pub fn save_secret_key_info(conn: &Connection, info: &SecretKeyInfo) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO secret_key (id, source, salt, verifier) VALUES (1, ?1, ?2, ?3)",
+        params![info.source, info.salt, info.verifier],
+    )?;
+    Ok(())
+}
+
+pub fn load_secret_key_info(conn: &Connection) -> Result<Option<SecretKeyInfo>> {
+    conn.query_row(
+        "SELECT source, salt, verifier FROM secret_key WHERE id = 1",
+        [],
+        |row| Ok(SecretKeyInfo {
+            source: row.get(0)?,
+            salt: row.get(1)?,
+            verifier: row.get(2)?,
+        })
+    ).optional()
+}
+
+pub fn settings_saved(conn: &Connection) -> Result<bool> {
+    let count: i64 = conn.query_row("SELECT COUNT(*) FROM settings WHERE id = 1", [], |row| row.get(0))?;
+    Ok(count > 0)
+}
+
+pub fn load_config_message_packs(conn: &Connection) -> Result<Vec<(i64, i64)>> {
+    let mut stmt = conn.prepare("SELECT config_id, pack_id FROM config_message_packs")?;
+    let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    rows.collect()
+}
+
+pub fn load_export_path_message_packs(conn: &Connection) -> Result<Vec<(String, i64)>> {
+    let mut stmt = conn.prepare("SELECT path, pack_id FROM export_path_message_packs")?;
+    let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    rows.collect()
+}
+
+pub fn load_export_path_maps(conn: &Connection) -> Result<Vec<ExportPathMap>> {
+    let mut stmt = conn.prepare("SELECT path, map, drop_incompatible FROM export_path_maps")?;
+    let rows = stmt.query_map([], |row| Ok(ExportPathMap { path: row.get(0)?, map: row.get(1)?, drop_incompatible: row.get(2)? }))?;
+    rows.collect()
+}
+
+// Everything a workspace restore in replace mode overwrites; the secrets key stays
+pub fn clear_workspace(conn: &Connection) -> Result<()> {
+    for table in ["saved_configs", "message_packs", "config_message_packs", "export_path_message_packs", "export_path_maps", "settings", "license_info", "permissions_source", "command_deny_list"] {
+        conn.execute(&format!("DELETE FROM {}", table), [])?;
+    }
+    Ok(())
+}
+
+pub fn save_backup_schedule(conn: &Connection, schedule: Option<&BackupSchedule>) -> Result<()> {
+    match schedule {
+        Some(schedule) => conn.execute(
+            "INSERT OR REPLACE INTO workspace_backup_schedule (id, directory, interval_hours, retention, last_backup) VALUES (1, ?1, ?2, ?3, ?4)",
+            params![schedule.directory, schedule.interval_hours, schedule.retention, schedule.last_backup.map(|time| time.to_rfc3339())],
+        )?,
+        None => conn.execute("DELETE FROM workspace_backup_schedule WHERE id = 1", [])?,
+    };
+    Ok(())
+}
+
+pub fn load_backup_schedule(conn: &Connection) -> Result<Option<BackupSchedule>> {
+    conn.query_row(
+        "SELECT directory, interval_hours, retention, last_backup FROM workspace_backup_schedule WHERE id = 1",
+        [],
+        |row| {
+            let last_backup: Option<String> = row.get(3)?;
+            Ok(BackupSchedule {
+                directory: row.get(0)?,
+                interval_hours: row.get(1)?,
+                retention: row.get(2)?,
+                last_backup: last_backup
+                    .and_then(|time| DateTime::parse_from_rfc3339(&time).ok())
+                    .map(|time| time.with_timezone(&Utc)),
+            })
+        }
+    ).optional()
+}
+
+pub fn set_last_backup(conn: &Connection, time: DateTime<Utc>) -> Result<()> {
+    conn.execute(
+        "UPDATE workspace_backup_schedule SET last_backup = ?1 WHERE id = 1",
+        params![time.to_rfc3339()],
+    )?;
+    Ok(())
+}
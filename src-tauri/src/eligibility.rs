@@ -0,0 +1,228 @@
+// src-tauri/src/eligibility.rs
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::permission_groups::parse_permissions;
+
+// Every player is in the Permissions plugin's Default group. Group names are
+// matched case-insensitively, the same as ArkApi.
+pub const DEFAULT_GROUP: &str = "Default";
+const DEFAULT_MIN_LEVEL: i64 = 1;
+const DEFAULT_MAX_LEVEL: i64 = 999;
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct PlayerProfile {
+    pub level: i64,
+    #[serde(default)]
+    pub groups: Vec<String>,
+}
+
+impl PlayerProfile {
+    pub fn in_group(&self, group: &str) -> bool {
+        group.eq_ignore_ascii_case(DEFAULT_GROUP) || self.groups.iter().any(|own| own.eq_ignore_ascii_case(group))
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct EligibleEntry {
+    pub key: String,
+    pub entry_type: Option<String>,
+    pub price: Option<f64>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct EligibleKit {
+    pub key: String,
+    pub default_amount: i64,
+    pub price: Option<f64>,
+    pub is_default_kit: bool,
+}
+
+#[derive(Serialize, Debug)]
+pub struct EligibilityReport {
+    pub shop_entries: Vec<EligibleEntry>,
+    pub kits: Vec<EligibleKit>,
+    pub default_kit: Option<String>,
+    pub default_kit_available: bool,
+}
+
+#[derive(Serialize, Debug)]
+pub struct UnreachableEntry {
+    pub section: String,
+    pub key: String,
+    pub reasons: Vec<String>,
+}
+
+pub fn level_window(entry: &Value) -> (i64, i64) {
+    let min = entry.get("MinLevel").and_then(Value::as_i64).unwrap_or(DEFAULT_MIN_LEVEL);
+    let max = entry.get("MaxLevel").and_then(Value::as_i64).unwrap_or(DEFAULT_MAX_LEVEL);
+    (min, max)
+}
+
+pub fn entry_groups(entry: &Value) -> Vec<String> {
+    entry.get("Permissions").and_then(Value::as_str).map(parse_permissions).unwrap_or_default()
+}
+
+// Why a player can't use an entry, or None when they can
+pub fn ineligibility(entry: &Value, player: &PlayerProfile) -> Option<String> {
+    let (min, max) = level_window(entry);
+    if player.level < min || player.level > max {
+        return Some(format!("Requires level {} - {}", min, max));
+    }
+
+    let groups = entry_groups(entry);
+    if !groups.is_empty() && !groups.iter().any(|group| player.in_group(group)) {
+        return Some(format!("Requires one of: {}", groups.join(", ")));
+    }
+
+    None
+}
+
+pub fn eligibility_report(config: &Value, player: &PlayerProfile) -> EligibilityReport {
+    let shop_entries = config
+        .get("ShopItems")
+        .and_then(Value::as_object)
+        .map(|entries| {
+            entries
+                .iter()
+                .filter(|(_, entry)| ineligibility(entry, player).is_none())
+                .map(|(key, entry)| EligibleEntry {
+                    key: key.clone(),
+                    entry_type: entry.get("Type").and_then(Value::as_str).map(str::to_string),
+                    price: entry.get("Price").and_then(Value::as_f64),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let default_kit = config
+        .pointer("/General/DefaultKit")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|kit| !kit.is_empty())
+        .map(str::to_string);
+
+    let kits: Vec<EligibleKit> = config
+        .get("Kits")
+        .and_then(Value::as_object)
+        .map(|entries| {
+            entries
+                .iter()
+                .filter(|(_, kit)| ineligibility(kit, player).is_none())
+                .map(|(key, kit)| EligibleKit {
+                    key: key.clone(),
+                    default_amount: kit.get("DefaultAmount").and_then(Value::as_i64).unwrap_or(0),
+                    price: kit.get("Price").and_then(Value::as_f64),
+                    is_default_kit: default_kit.as_ref().map_or(false, |default| default.eq_ignore_ascii_case(key)),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let default_kit_available = kits.iter().any(|kit| kit.is_default_kit);
+    EligibilityReport {
+        shop_entries,
+        kits,
+        default_kit,
+        default_kit_available,
+    }
+}
+
+// Entries no player can ever use: an empty level window, or only unknown groups.
+// Group checks are skipped when the server's group list isn't known.
+pub fn unreachable_entries(config: &Value, known_groups: Option<&[String]>) -> Vec<UnreachableEntry> {
+    let mut unreachable = Vec::new();
+
+    for section in ["ShopItems", "Kits"] {
+        let entries = match config.get(section).and_then(Value::as_object) {
+            Some(entries) => entries,
+            None => continue,
+        };
+
+        for (key, entry) in entries {
+            let mut reasons = Vec::new();
+
+            let (min, max) = level_window(entry);
+            if min > max {
+                reasons.push(format!("MinLevel {} is above MaxLevel {}", min, max));
+            } else if max < DEFAULT_MIN_LEVEL {
+                reasons.push(format!("MaxLevel {} is below level {}", max, DEFAULT_MIN_LEVEL));
+            }
+
+            if let Some(known) = known_groups {
+                let groups = entry_groups(entry);
+                let is_known = |group: &String| {
+                    group.eq_ignore_ascii_case(DEFAULT_GROUP) || known.iter().any(|server| server.eq_ignore_ascii_case(group))
+                };
+                if !groups.is_empty() && !groups.iter().any(is_known) {
+                    reasons.push(format!("None of its groups exist on the server: {}", groups.join(", ")));
+                }
+            }
+
+            if !reasons.is_empty() {
+                unreachable.push(UnreachableEntry {
+                    section: section.to_string(),
+                    key: key.clone(),
+                    reasons,
+                });
+            }
+        }
+    }
+
+    unreachable
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn player(level: i64, groups: &[&str]) -> PlayerProfile {
+        PlayerProfile {
+            level,
+            groups: groups.iter().map(|group| group.to_string()).collect(),
+        }
+    }
+
+    fn config() -> Value {
+        json!({
+            "General": { "DefaultKit": "starter" },
+            "ShopItems": {
+                "everyone": { "Type": "item", "Price": 10, "Permissions": "default" },
+                "vip": { "Type": "dino", "Price": 500, "Permissions": "VIP" },
+                "veteran": { "Type": "item", "Price": 50, "MinLevel": 50 }
+            },
+            "Kits": {
+                "starter": { "DefaultAmount": 1, "Permissions": "Default" },
+                "ghost": { "DefaultAmount": 1, "Permissions": "Nobody" },
+                "broken": { "DefaultAmount": 1, "MinLevel": 20, "MaxLevel": 10 }
+            }
+        })
+    }
+
+    #[test]
+    fn lowercase_default_is_open_to_everyone() {
+        let report = eligibility_report(&config(), &player(1, &[]));
+        let keys: Vec<&str> = report.shop_entries.iter().map(|entry| entry.key.as_str()).collect();
+        assert_eq!(keys, vec!["everyone"]);
+        assert!(report.default_kit_available);
+    }
+
+    #[test]
+    fn matches_player_groups_case_insensitively() {
+        let report = eligibility_report(&config(), &player(60, &["vip"]));
+        let keys: Vec<&str> = report.shop_entries.iter().map(|entry| entry.key.as_str()).collect();
+        assert_eq!(keys, vec!["everyone", "vip", "veteran"]);
+        assert_eq!(ineligibility(&config()["ShopItems"]["veteran"], &player(10, &[])), Some("Requires level 50 - 999".to_string()));
+    }
+
+    #[test]
+    fn reports_unreachable_entries() {
+        let known = vec!["Vip".to_string()];
+        let unreachable = unreachable_entries(&config(), Some(&known));
+        let keys: Vec<&str> = unreachable.iter().map(|entry| entry.key.as_str()).collect();
+        assert_eq!(keys, vec!["ghost", "broken"]);
+        assert_eq!(unreachable[0].reasons, vec!["None of its groups exist on the server: Nobody"]);
+        assert_eq!(unreachable[1].reasons, vec!["MinLevel 20 is above MaxLevel 10"]);
+    }
+}
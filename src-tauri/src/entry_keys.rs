@@ -0,0 +1,287 @@
+// src-tauri/src/entry_keys.rs
+
+use std::collections::{BTreeMap, HashSet};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use crate::config_refs::ENTRY_SECTIONS;
+use crate::message_schema::default_config;
+
+const DEFAULT_KIT: &str = "/General/DefaultKit";
+pub const MAX_KEY_LENGTH: usize = 32;
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum KeyProblem {
+    Empty,
+    IllegalCharacters,
+    TooLong,
+    CaseDuplicate,
+    CommandClash,
+}
+
+#[derive(Serialize, Debug)]
+pub struct KeyIssue {
+    pub section: String,
+    pub key: String,
+    pub problem: KeyProblem,
+    pub message: String,
+    pub suggestion: String,
+}
+
+// Other places in the config that refer to an entry by its key
+fn key_references(section: &str) -> &'static [&'static str] {
+    match section {
+        "Kits" => &[DEFAULT_KIT],
+        _ => &[],
+    }
+}
+
+fn section_entries<'a>(config: &'a Value, section: &str) -> Result<&'a Map<String, Value>, String> {
+    if !ENTRY_SECTIONS.contains(&section) {
+        return Err(format!("\"{}\" is not a renameable section", section));
+    }
+    config
+        .get(section)
+        .and_then(Value::as_object)
+        .ok_or_else(|| format!("Config has no {} section", section))
+}
+
+// Renames an entry in place, keeping its position, and returns the references it rewrote
+pub fn rename_entry_key(config: &mut Value, section: &str, from: &str, to: &str) -> Result<Vec<String>, String> {
+    let to = to.trim();
+    if to.is_empty() {
+        return Err("New key must not be empty".to_string());
+    }
+
+    let entries = section_entries(config, section)?;
+    if !entries.contains_key(from) {
+        return Err(format!("{} has no entry \"{}\"", section, from));
+    }
+    if from == to {
+        return Ok(Vec::new());
+    }
+    if let Some(existing) = entries.keys().find(|key| key.as_str() != from && key.eq_ignore_ascii_case(to)) {
+        return Err(format!("{} already has an entry \"{}\" (keys are case-insensitive for players)", section, existing));
+    }
+
+    if let Some(entries) = config.get_mut(section).and_then(Value::as_object_mut) {
+        let renamed: Map<String, Value> = std::mem::take(entries)
+            .into_iter()
+            .map(|(key, entry)| if key == from { (to.to_string(), entry) } else { (key, entry) })
+            .collect();
+        *entries = renamed;
+    }
+
+    let mut rewritten = Vec::new();
+    for reference in key_references(section) {
+        if let Some(Value::String(value)) = config.pointer_mut(reference) {
+            if value.trim().eq_ignore_ascii_case(from) {
+                *value = to.to_string();
+                rewritten.push(reference.to_string());
+            }
+        }
+    }
+
+    Ok(rewritten)
+}
+
+// Keys are typed after /buy, /kit and /sell, so stick to what chat handles cleanly
+fn is_allowed_key_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '-'
+}
+
+// Command words from the *Cmd messages, without their leading slash
+fn command_words(config: &Value) -> HashSet<String> {
+    let mut words = HashSet::new();
+    for source in [config, &default_config()] {
+        if let Some(messages) = source.get("Messages").and_then(Value::as_object) {
+            for (key, value) in messages {
+                if !key.ends_with("Cmd") {
+                    continue;
+                }
+                if let Some(command) = value.as_str() {
+                    let word = command.trim().trim_start_matches('/').to_lowercase();
+                    if !word.is_empty() {
+                        words.insert(word);
+                    }
+                }
+            }
+        }
+    }
+    words
+}
+
+pub fn suggest_key(key: &str, taken: &HashSet<String>, reserved: &HashSet<String>) -> String {
+    let mut base: String = key
+        .trim()
+        .chars()
+        .map(|c| if c.is_whitespace() { '_' } else { c })
+        .filter(|c| is_allowed_key_char(*c))
+        .collect();
+    if base.is_empty() {
+        base = "entry".to_string();
+    }
+    base.truncate(MAX_KEY_LENGTH);
+
+    let is_free = |candidate: &str| {
+        let lower = candidate.to_lowercase();
+        !taken.contains(&lower) && !reserved.contains(&lower)
+    };
+    if is_free(&base) {
+        return base;
+    }
+
+    let mut counter = 2;
+    loop {
+        let suffix = format!("_{}", counter);
+        let mut candidate = base.clone();
+        candidate.truncate(MAX_KEY_LENGTH - suffix.len());
+        candidate.push_str(&suffix);
+        if is_free(&candidate) {
+            return candidate;
+        }
+        counter += 1;
+    }
+}
+
+pub fn validate_entry_keys(config: &Value) -> Vec<KeyIssue> {
+    let reserved = command_words(config);
+    let mut issues = Vec::new();
+
+    for section in ENTRY_SECTIONS.iter() {
+        let entries = match config.get(*section).and_then(Value::as_object) {
+            Some(entries) => entries,
+            None => continue,
+        };
+
+        let mut by_lowercase: BTreeMap<String, Vec<&String>> = BTreeMap::new();
+        for key in entries.keys() {
+            by_lowercase.entry(key.to_lowercase()).or_default().push(key);
+        }
+
+        // Suggestions must not collide with existing keys or with each other
+        let mut taken: HashSet<String> = by_lowercase.keys().cloned().collect();
+
+        for key in entries.keys() {
+            let mut problems = Vec::new();
+
+            if key.trim().is_empty() {
+                problems.push((KeyProblem::Empty, "Key is empty, players can't type it".to_string()));
+            } else if !key.chars().all(is_allowed_key_char) {
+                let mut illegal: Vec<String> = Vec::new();
+                for c in key.chars().filter(|c| !is_allowed_key_char(*c)) {
+                    let quoted = format!("\"{}\"", c);
+                    if !illegal.contains(&quoted) {
+                        illegal.push(quoted);
+                    }
+                }
+                problems.push((KeyProblem::IllegalCharacters, format!("Key contains characters players can't reliably type: {}", illegal.join(", "))));
+            }
+
+            if key.chars().count() > MAX_KEY_LENGTH {
+                problems.push((KeyProblem::TooLong, format!("Key is longer than {} characters", MAX_KEY_LENGTH)));
+            }
+
+            let lower = key.to_lowercase();
+            let same_case_insensitive = &by_lowercase[&lower];
+            if same_case_insensitive.len() > 1 && same_case_insensitive[0] != key {
+                problems.push((KeyProblem::CaseDuplicate, format!("Key only differs by case from \"{}\"", same_case_insensitive[0])));
+            }
+
+            if reserved.contains(&lower) {
+                problems.push((KeyProblem::CommandClash, format!("Key \"{}\" clashes with a chat command word", key)));
+            }
+
+            if problems.is_empty() {
+                continue;
+            }
+
+            let suggestion = suggest_key(key, &taken, &reserved);
+            taken.insert(suggestion.to_lowercase());
+            for (problem, message) in problems {
+                issues.push(KeyIssue {
+                    section: section.to_string(),
+                    key: key.clone(),
+                    problem,
+                    message,
+                    suggestion: suggestion.clone(),
+                });
+            }
+        }
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn config() -> Value {
+        json!({
+            "General": { "DefaultKit": "Starter " },
+            "Kits": {
+                "vip": { "DefaultAmount": 0 },
+                "starter": { "DefaultAmount": 1 },
+                "pvp": { "DefaultAmount": 0 }
+            },
+            "ShopItems": {
+                "starter": { "Type": "item" },
+                "metal": { "Type": "item" }
+            }
+        })
+    }
+
+    fn keys(config: &Value, section: &str) -> Vec<String> {
+        config[section].as_object().unwrap().keys().cloned().collect()
+    }
+
+    #[test]
+    fn renaming_a_kit_rewrites_the_default_kit() {
+        let mut config = config();
+        let rewritten = rename_entry_key(&mut config, "Kits", "starter", " newbie ").unwrap();
+
+        assert_eq!(rewritten, vec![DEFAULT_KIT.to_string()]);
+        assert_eq!(config["General"]["DefaultKit"], "newbie");
+        // Position in the section is kept
+        assert_eq!(keys(&config, "Kits"), vec!["vip", "newbie", "pvp"]);
+        assert_eq!(config["Kits"]["newbie"]["DefaultAmount"], 1);
+    }
+
+    #[test]
+    fn only_matching_references_are_rewritten() {
+        let mut config = config();
+        assert_eq!(rename_entry_key(&mut config, "Kits", "pvp", "arena").unwrap(), Vec::<String>::new());
+        assert_eq!(config["General"]["DefaultKit"], "Starter ");
+
+        // DefaultKit names a kit, not a shop entry with the same key
+        assert_eq!(rename_entry_key(&mut config, "ShopItems", "starter", "starter_pack").unwrap(), Vec::<String>::new());
+        assert_eq!(config["General"]["DefaultKit"], "Starter ");
+        assert_eq!(keys(&config, "ShopItems"), vec!["starter_pack", "metal"]);
+    }
+
+    #[test]
+    fn rejects_case_insensitive_collisions() {
+        let mut config = config();
+        assert_eq!(
+            rename_entry_key(&mut config, "Kits", "pvp", "VIP").unwrap_err(),
+            "Kits already has an entry \"vip\" (keys are case-insensitive for players)",
+        );
+        assert_eq!(keys(&config, "Kits"), vec!["vip", "starter", "pvp"]);
+
+        // Changing only the case of the key itself is fine
+        assert_eq!(rename_entry_key(&mut config, "Kits", "starter", "Starter").unwrap(), vec![DEFAULT_KIT.to_string()]);
+        assert_eq!(keys(&config, "Kits"), vec!["vip", "Starter", "pvp"]);
+    }
+
+    #[test]
+    fn rejects_bad_renames() {
+        let mut config = config();
+        assert_eq!(rename_entry_key(&mut config, "Kits", "starter", "  ").unwrap_err(), "New key must not be empty");
+        assert_eq!(rename_entry_key(&mut config, "Kits", "missing", "other").unwrap_err(), "Kits has no entry \"missing\"");
+        assert_eq!(rename_entry_key(&mut config, "General", "DefaultKit", "other").unwrap_err(), "\"General\" is not a renameable section");
+        assert_eq!(rename_entry_key(&mut config, "SellItems", "stone", "rock").unwrap_err(), "Config has no SellItems section");
+        assert_eq!(rename_entry_key(&mut config, "Kits", "starter", "starter").unwrap(), Vec::<String>::new());
+    }
+}
@@ -0,0 +1,277 @@
+// src-tauri/src/entry_order.rs
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use crate::config_refs::ENTRY_SECTIONS;
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum SortField {
+    Price,
+    Type,
+    Name,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum OrderOperation {
+    Move {
+        key: String,
+        index: usize,
+    },
+    Sort {
+        by: SortField,
+        #[serde(default)]
+        descending: bool,
+    },
+    GroupByCategory,
+}
+
+// Maps catalog blueprints and beacon class names to their catalog Type,
+// e.g. "Weapons" for an item or "creature" for a dino
+pub fn catalog_categories(catalog: &Value) -> HashMap<String, String> {
+    let mut categories = HashMap::new();
+    for (section, field) in [("Items", "Blueprint"), ("Dinos", "Blueprint"), ("Engrams", "Blueprint"), ("Beacons", "ClassName")] {
+        if let Some(entries) = catalog.get(section).and_then(Value::as_object) {
+            for entry in entries.values() {
+                if let (Some(reference), Some(category)) = (
+                    entry.get(field).and_then(Value::as_str),
+                    entry.get("Type").and_then(Value::as_str),
+                ) {
+                    categories.insert(reference.trim().to_string(), category.to_string());
+                }
+            }
+        }
+    }
+    categories
+}
+
+fn first_reference(entry: &Value) -> Option<&str> {
+    if let Some(reference) = entry.get("Blueprint").or_else(|| entry.get("ClassName")).and_then(Value::as_str) {
+        return Some(reference);
+    }
+    ["Items", "Dinos"].iter().find_map(|list| {
+        entry
+            .get(*list)
+            .and_then(Value::as_array)
+            .and_then(|items| items.first())
+            .and_then(|item| item.get("Blueprint"))
+            .and_then(Value::as_str)
+    })
+}
+
+pub fn entry_category(entry: &Value, categories: &HashMap<String, String>) -> String {
+    first_reference(entry)
+        .and_then(|reference| categories.get(reference.trim()))
+        .cloned()
+        .or_else(|| entry.get("Type").and_then(Value::as_str).map(str::to_string))
+        .unwrap_or_else(|| "Other".to_string())
+}
+
+// Entries without a price stay last whichever way the sort runs
+fn compare_entries(a: &(String, Value), b: &(String, Value), by: SortField, descending: bool) -> Ordering {
+    let directed = |ordering: Ordering| if descending { ordering.reverse() } else { ordering };
+    match by {
+        SortField::Price => {
+            let price = |entry: &Value| entry.get("Price").and_then(Value::as_f64);
+            match (price(&a.1), price(&b.1)) {
+                (Some(x), Some(y)) => directed(x.partial_cmp(&y).unwrap_or(Ordering::Equal)),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            }
+        }
+        SortField::Type => {
+            let kind = |entry: &Value| entry.get("Type").and_then(Value::as_str).unwrap_or_default().to_lowercase();
+            directed(kind(&a.1).cmp(&kind(&b.1)))
+        }
+        SortField::Name => {
+            // The Title players see, or the key for entries without one
+            let name = |(key, entry): &(String, Value)| {
+                entry
+                    .get("Title")
+                    .and_then(Value::as_str)
+                    .filter(|title| !title.trim().is_empty())
+                    .unwrap_or(key)
+                    .trim()
+                    .to_lowercase()
+            };
+            directed(name(a).cmp(&name(b)))
+        }
+    }
+}
+
+pub fn reorder_entries(config: &mut Value, section: &str, operation: &OrderOperation, categories: &HashMap<String, String>) -> Result<(), String> {
+    if !ENTRY_SECTIONS.contains(&section) {
+        return Err(format!("\"{}\" can't be reordered", section));
+    }
+    let entries = config
+        .get_mut(section)
+        .and_then(Value::as_object_mut)
+        .ok_or_else(|| format!("Config has no {} section", section))?;
+
+    let mut ordered: Vec<(String, Value)> = std::mem::take(entries).into_iter().collect();
+
+    match operation {
+        OrderOperation::Move { key, index } => {
+            match ordered.iter().position(|(existing, _)| existing == key) {
+                Some(from) => {
+                    let entry = ordered.remove(from);
+                    let to = (*index).min(ordered.len());
+                    ordered.insert(to, entry);
+                }
+                None => {
+                    *entries = ordered.into_iter().collect();
+                    return Err(format!("{} has no entry \"{}\"", section, key));
+                }
+            }
+        }
+        OrderOperation::Sort { by, descending } => {
+            ordered.sort_by(|a, b| compare_entries(a, b, *by, *descending));
+        }
+        OrderOperation::GroupByCategory => {
+            // Groups appear in order of their first entry, and entries keep their order within a group
+            let mut group_order: Vec<String> = Vec::new();
+            let mut grouped: HashMap<String, Vec<(String, Value)>> = HashMap::new();
+            for (key, entry) in ordered.drain(..) {
+                let category = entry_category(&entry, categories);
+                if !grouped.contains_key(&category) {
+                    group_order.push(category.clone());
+                }
+                grouped.entry(category).or_default().push((key, entry));
+            }
+            for category in group_order {
+                ordered.extend(grouped.remove(&category).unwrap_or_default());
+            }
+        }
+    }
+
+    *entries = ordered.into_iter().collect::<Map<String, Value>>();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rusqlite::Connection;
+    use serde_json::json;
+    use std::path::Path;
+    use crate::db::{load_config_by_id, save_config, save_message_pack, set_export_path_message_pack, MessagePack, SavedConfig};
+    use crate::message_packs::config_for_export;
+    use crate::migrations::migrate;
+
+    const METAL: &str = "Blueprint'/Game/PrimalEarth/CoreBlueprints/Resources/PrimalItemResource_MetalIngot.PrimalItemResource_MetalIngot'";
+    const RIFLE: &str = "Blueprint'/Game/PrimalEarth/CoreBlueprints/Weapons/PrimalItem_WeaponRifle.PrimalItem_WeaponRifle'";
+    const RAPTOR: &str = "Blueprint'/Game/PrimalEarth/Dinos/Raptor/Raptor_Character_BP.Raptor_Character_BP'";
+
+    fn config() -> Value {
+        json!({
+            "Messages": { "BoughtItem": "You bought {0}" },
+            "ShopItems": {
+                "rifle": { "Type": "item", "Title": "Longneck", "Price": 300, "Items": [{ "Blueprint": RIFLE }] },
+                "metal": { "Type": "item", "Title": "Metal Ingot", "Price": 20, "Items": [{ "Blueprint": METAL }] },
+                "free": { "Type": "command", "Title": "", "Items": [{ "Command": "heal" }] },
+                "raptor": { "Type": "dino", "Price": 500, "Blueprint": RAPTOR },
+                "ammo": { "Type": "item", "Title": "Advanced Rifle Bullet", "Price": 20, "Items": [{ "Blueprint": RIFLE }] },
+                "xp": { "Type": "experience", "Title": "Experience" }
+            }
+        })
+    }
+
+    fn keys(config: &Value) -> Vec<&str> {
+        config["ShopItems"].as_object().unwrap().keys().map(String::as_str).collect()
+    }
+
+    fn reordered(operation: OrderOperation) -> Value {
+        let mut config = config();
+        let categories = catalog_categories(&json!({
+            "Items": {
+                "Metal Ingot": { "Blueprint": METAL, "Type": "Resources" },
+                "Longneck Rifle": { "Blueprint": RIFLE, "Type": "Weapons" }
+            }
+        }));
+        reorder_entries(&mut config, "ShopItems", &operation, &categories).unwrap();
+        config
+    }
+
+    #[test]
+    fn moves_an_entry_to_an_index() {
+        let config = reordered(OrderOperation::Move { key: "raptor".to_string(), index: 0 });
+        assert_eq!(keys(&config), vec!["raptor", "rifle", "metal", "free", "ammo", "xp"]);
+        // Past the end means last
+        let config = reordered(OrderOperation::Move { key: "rifle".to_string(), index: 99 });
+        assert_eq!(keys(&config), vec!["metal", "free", "raptor", "ammo", "xp", "rifle"]);
+
+        let mut config = config;
+        let err = reorder_entries(&mut config, "ShopItems", &OrderOperation::Move { key: "missing".to_string(), index: 0 }, &HashMap::new()).unwrap_err();
+        assert_eq!(err, "ShopItems has no entry \"missing\"");
+        // A failed move leaves the section as it was
+        assert_eq!(keys(&config), vec!["metal", "free", "raptor", "ammo", "xp", "rifle"]);
+    }
+
+    #[test]
+    fn price_sort_keeps_entries_without_a_price_last() {
+        let ascending = reordered(OrderOperation::Sort { by: SortField::Price, descending: false });
+        // Equal prices keep their order
+        assert_eq!(keys(&ascending), vec!["metal", "ammo", "rifle", "raptor", "free", "xp"]);
+        let descending = reordered(OrderOperation::Sort { by: SortField::Price, descending: true });
+        assert_eq!(keys(&descending), vec!["raptor", "rifle", "metal", "ammo", "free", "xp"]);
+    }
+
+    #[test]
+    fn name_sort_uses_the_title_then_the_key() {
+        let ascending = reordered(OrderOperation::Sort { by: SortField::Name, descending: false });
+        assert_eq!(keys(&ascending), vec!["ammo", "xp", "free", "rifle", "metal", "raptor"]);
+        let descending = reordered(OrderOperation::Sort { by: SortField::Name, descending: true });
+        assert_eq!(keys(&descending), vec!["raptor", "metal", "rifle", "free", "xp", "ammo"]);
+    }
+
+    #[test]
+    fn type_sort_and_grouping_keep_order_within_a_group() {
+        let by_type = reordered(OrderOperation::Sort { by: SortField::Type, descending: false });
+        assert_eq!(keys(&by_type), vec!["free", "raptor", "xp", "rifle", "metal", "ammo"]);
+
+        // Catalog categories where the blueprint is known, the entry Type otherwise
+        let grouped = reordered(OrderOperation::GroupByCategory);
+        assert_eq!(keys(&grouped), vec!["rifle", "ammo", "metal", "free", "raptor", "xp"]);
+    }
+
+    #[test]
+    fn rejects_sections_that_cant_be_reordered() {
+        let mut config = config();
+        assert_eq!(reorder_entries(&mut config, "Messages", &OrderOperation::GroupByCategory, &HashMap::new()).unwrap_err(), "\"Messages\" can't be reordered");
+        assert_eq!(reorder_entries(&mut config, "Kits", &OrderOperation::GroupByCategory, &HashMap::new()).unwrap_err(), "Config has no Kits section");
+    }
+
+    #[test]
+    fn order_survives_save_and_export() {
+        let config = reordered(OrderOperation::Sort { by: SortField::Price, descending: true });
+        let expected = vec!["raptor", "rifle", "metal", "ammo", "free", "xp"];
+
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrate(&mut conn, Path::new("unused.db")).unwrap();
+        let id = save_config(&conn, &SavedConfig {
+            id: None,
+            name: "main".to_string(),
+            config: serde_json::to_string(&config).unwrap(),
+            custom_export_paths: None,
+        }).unwrap();
+        let saved: Value = serde_json::from_str(&load_config_by_id(&conn, id).unwrap().unwrap().config).unwrap();
+        assert_eq!(keys(&saved), expected);
+
+        // A message pack makes the export rebuild the config rather than copy it
+        let pack = save_message_pack(&conn, &MessagePack {
+            id: None,
+            name: "English".to_string(),
+            messages: json!({ "BoughtItem": "Bought {0}" }).to_string(),
+            is_default: false,
+        }).unwrap();
+        set_export_path_message_pack(&conn, "C:\\server", Some(pack)).unwrap();
+        let exported = config_for_export(&conn, &saved, Some(id), "C:\\server").unwrap();
+        assert_eq!(exported["Messages"]["BoughtItem"], "Bought {0}");
+        let written: Value = serde_json::from_str(&serde_json::to_string_pretty(&exported).unwrap()).unwrap();
+        assert_eq!(keys(&written), expected);
+    }
+}
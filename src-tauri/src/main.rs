@@ -6,12 +6,57 @@
 )]
 
 mod db;
+mod migrations;
 mod ark_data;
 mod hwid;
 mod license;
+mod config_refs;
+mod catalog_diff;
+mod rich_color;
+mod message_schema;
+mod message_packs;
+mod permission_groups;
+mod permissions_db;
+mod entry_keys;
+mod entry_order;
+mod shop_preview;
+mod eligibility;
+mod simulator;
+mod blueprint;
+mod beacon_maps;
+mod shop_validation;
+mod command_safety;
+mod spreadsheet;
+mod catalog_export;
+mod config_schema;
+mod secrets;
+mod bundle;
+mod workspace;
 
-use db::{get_database_path, initialize_db, save_settings, load_settings, Settings, SavedConfig, save_config, load_configs, delete_config, config_name_exists, update_config, LicenseInfo, save_license_info, load_license_info, load_current_config, load_config_by_id, update_config_export_paths};
-use ark_data::read_ark_data;
+use db::{get_database_path, default_database_path, load_database_override, save_database_override, migrate_database_location, initialize_db, save_settings, load_settings, Settings, SavedConfig, save_config, load_configs, delete_config, config_name_exists, update_config, LicenseInfo, save_license_info, load_license_info, load_current_config, load_config_by_id, update_config_export_paths, MessagePack, save_message_pack, update_message_pack, load_message_packs, load_message_pack_by_id, message_pack_name_exists, set_default_message_pack, delete_message_pack, set_config_message_pack, load_config_message_pack, set_export_path_message_pack, load_export_path_message_pack, save_permissions_source, load_permissions_source, ExportPathMap, set_export_path_map, load_export_path_map, save_command_deny_list, load_command_deny_list, SecretKeyInfo, save_secret_key_info, load_secret_key_info, BackupSchedule, save_backup_schedule, load_backup_schedule, set_last_backup};
+use ark_data::{read_ark_data, load_ark_data, load_ark_data_from_app, catalog_override_dir, list_catalog_sources, bundled_catalog_path, CatalogLoadReport, CatalogSource};
+use catalog_diff::{diff_catalogs, config_impact, CatalogDiff, ConfigImpact};
+use rich_color::{parse_rich_text, validate_messages_markup, ParsedMessage, MessageMarkupIssue};
+use message_schema::{message_schema, validate_message_placeholders, MessageSchema, PlaceholderIssue};
+use message_packs::{config_for_export, read_message_pack_file, write_message_pack_file};
+use permission_groups::{group_inventory, rename_group, GroupUsage};
+use permissions_db::{read_permission_groups, find_unknown_groups, PermissionsSource, UnknownGroup};
+use entry_keys::{rename_entry_key, validate_entry_keys, KeyIssue};
+use entry_order::{reorder_entries, catalog_categories, OrderOperation};
+use shop_preview::{preview_shop, ShopPreview};
+use eligibility::{eligibility_report, unreachable_entries, EligibilityReport, PlayerProfile, UnreachableEntry};
+use simulator::{run_scenario, run_scenario_files, Scenario, ScenarioResult};
+use blueprint::{class_index, normalize_config_blueprints, validate_config_blueprints, BlueprintIssue, NormalizeReport};
+use beacon_maps::{catalog_beacons, catalog_maps, check_beacon_maps, drop_incompatible_beacons, BeaconMapIssue, CatalogBeacon};
+use shop_validation::{validate_shop_entries, CatalogBlueprints, EntryIssue};
+use command_safety::{analyze_commands, default_deny_list, denied_entries, CommandIssue};
+use spreadsheet::{apply_sheet_diff, import_sheet, read_sheet, sheet_rows, write_csv, write_xlsx, SheetDiff, SheetImport};
+use catalog_export::{export_catalog, CatalogFormat, CatalogTemplates, RenderedCatalog, TemplateOverrides};
+use config_schema::{config_schema, CatalogEnums};
+use workspace::{collect_workspace, prune_backups, read_workspace, restore_workspace, scheduled_backup_name, stage_catalogs, unique_name, write_workspace, RestoreMode, RestoreReport, Workspace, WORKSPACE_EXTENSION};
+use bundle::{custom_catalog_entries, new_metadata, read_bundle, redact_config, write_bundle, BundleMetadata, BUNDLE_EXTENSION};
+use secrets::{clear_sealed_secrets, delete_keyring_key, derive_key, has_locked_placeholders, has_plain_secrets, has_sealed_secrets, load_keyring_key, mask_sealed_secrets, new_salt, open_secrets, random_key, restore_locked_secrets, seal_secrets, store_keyring_key, verifier, verify, KeySource, SecretKey, CONFIG_SECRETS, SOURCE_SECRETS};
+use std::collections::HashMap;
 use std::fs;
 use std::path::{PathBuf, Path};
 use serde_json::Value;
@@ -29,10 +74,15 @@ use tauri::Manager;
 
 struct LicenseState(Mutex<bool>);
 struct AppState(Mutex<Connection>);
+// Key for encrypted config secrets, once the keyring or passphrase has provided it
+struct SecretState(Mutex<Option<SecretKey>>);
 
 unsafe impl Send for AppState {}
 unsafe impl Sync for AppState {}
 
+// How often the backup thread checks whether a scheduled workspace backup is due
+const BACKUP_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(10 * 60);
+
 fn log_to_file(message: &str) {
     if cfg!(debug_assertions) {
         let log_path = env::current_exe()
@@ -59,6 +109,59 @@ fn save_settings_command(state: tauri::State<AppState>, output_path: String, aut
     Ok(())
 }
 
+#[derive(Serialize)]
+struct DatabaseLocation {
+    path: String,
+    default_path: Option<String>,
+    override_path: Option<String>,
+}
+
+#[tauri::command]
+fn get_database_location_command(app_handle: tauri::AppHandle) -> DatabaseLocation {
+    let display = |path: PathBuf| path.to_string_lossy().into_owned();
+    DatabaseLocation {
+        path: display(get_database_path(&app_handle)),
+        default_path: default_database_path(&app_handle).map(display),
+        override_path: load_database_override(&app_handle).map(display),
+    }
+}
+
+// Creating and removing a probe file is the only check that holds for network shares and ACLs
+fn ensure_writable(path: &Path) -> Result<(), String> {
+    let folder = path.parent().ok_or_else(|| format!("{} has no folder", path.display()))?;
+    let probe = folder.join(format!(".arkshop-write-check-{}", std::process::id()));
+    fs::write(&probe, b"").map_err(|e| format!("Can't write to {}: {}", folder.display(), e))?;
+    let _ = fs::remove_file(&probe);
+    if fs::metadata(path).map_or(false, |metadata| metadata.permissions().readonly()) {
+        return Err(format!("{} is read-only", path.display()));
+    }
+    Ok(())
+}
+
+// Takes effect on the next launch, which copies the current database over if the new location has none
+#[tauri::command]
+fn set_database_override_command(app_handle: tauri::AppHandle, state: tauri::State<AppState>, path: Option<String>) -> Result<(), String> {
+    let path = match path.filter(|path| !path.trim().is_empty()) {
+        Some(path) => {
+            let mut path = PathBuf::from(path.trim());
+            if path.is_dir() {
+                path.push("settings.db");
+            }
+            if !path.parent().map_or(false, Path::is_dir) {
+                return Err(format!("Folder for {} doesn't exist", path.display()));
+            }
+            ensure_writable(&path)?;
+            Some(path)
+        }
+        None => None,
+    };
+    let current = {
+        let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
+        conn.path().map(PathBuf::from).unwrap_or_else(|| get_database_path(&app_handle))
+    };
+    save_database_override(&app_handle, path.as_deref(), &current).map_err(|e| format!("Failed to save database location: {}", e))
+}
+
 #[tauri::command]
 fn load_settings_command(state: tauri::State<AppState>) -> Result<Settings, String> {
     let conn = state.0.lock().unwrap();
@@ -70,28 +173,910 @@ fn read_ark_data_command(app_handle: tauri::AppHandle) -> Result<Value, String>
     read_ark_data(app_handle)
 }
 
+#[tauri::command]
+fn read_ark_data_report_command(app_handle: tauri::AppHandle) -> CatalogLoadReport {
+    let report = load_ark_data_from_app(&app_handle).report;
+    if report.has_errors() {
+        log_to_file(&format!("Catalog loaded with {} issue(s), {} entries skipped", report.issues.len(), report.skipped));
+    }
+    report
+}
+
+#[tauri::command]
+fn list_catalog_sources_command(app_handle: tauri::AppHandle) -> Vec<CatalogSource> {
+    list_catalog_sources(&app_handle)
+}
+
+// Defaults compare the bundled catalog against the active one
+fn load_catalog_pair(app_handle: &tauri::AppHandle, from_path: Option<String>, to_path: Option<String>) -> Result<(Value, Value), String> {
+    let from = match from_path {
+        Some(path) => load_ark_data(Path::new(&path)),
+        None => load_ark_data(&bundled_catalog_path(app_handle)?),
+    };
+    let to = match to_path {
+        Some(path) => load_ark_data(Path::new(&path)),
+        None => load_ark_data_from_app(app_handle),
+    };
+
+    for load in [&from, &to] {
+        if !load.is_usable() {
+            return Err(format!("Catalog {} could not be loaded", load.report.source));
+        }
+    }
+    Ok((from.data, to.data))
+}
+
+#[tauri::command]
+fn diff_catalogs_command(app_handle: tauri::AppHandle, from_path: Option<String>, to_path: Option<String>) -> Result<CatalogDiff, String> {
+    let (from, to) = load_catalog_pair(&app_handle, from_path, to_path)?;
+    Ok(diff_catalogs(&from, &to))
+}
+
+#[tauri::command]
+fn catalog_impact_report_command(app_handle: tauri::AppHandle, state: tauri::State<AppState>, from_path: Option<String>, to_path: Option<String>) -> Result<Vec<ConfigImpact>, String> {
+    let (from, to) = load_catalog_pair(&app_handle, from_path, to_path)?;
+    let diff = diff_catalogs(&from, &to);
+
+    let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
+    let configs = load_configs(&conn).map_err(|e| e.to_string())?;
+
+    let mut impacts = Vec::new();
+    for saved in configs {
+        let config: Value = match serde_json::from_str(&saved.config) {
+            Ok(config) => config,
+            Err(e) => {
+                impacts.push(ConfigImpact {
+                    config_id: saved.id,
+                    config_name: saved.name,
+                    references: Vec::new(),
+                    parse_error: Some(e.to_string()),
+                });
+                continue;
+            }
+        };
+        let references = config_impact(&config, &diff);
+        if !references.is_empty() {
+            impacts.push(ConfigImpact {
+                config_id: saved.id,
+                config_name: saved.name,
+                references,
+                parse_error: None,
+            });
+        }
+    }
+    Ok(impacts)
+}
+
+#[tauri::command]
+fn preview_message_command(message: String) -> ParsedMessage {
+    parse_rich_text(&message)
+}
+
+#[tauri::command]
+fn validate_messages_markup_command(config: Value) -> Vec<MessageMarkupIssue> {
+    validate_messages_markup(&config)
+}
+
+#[tauri::command]
+fn get_message_schema_command() -> Vec<MessageSchema> {
+    message_schema().into_values().collect()
+}
+
+#[tauri::command]
+fn validate_message_placeholders_command(config: Value) -> Vec<PlaceholderIssue> {
+    validate_message_placeholders(&config)
+}
+
+#[tauri::command]
+fn permission_group_inventory_command(config: Value) -> Vec<GroupUsage> {
+    group_inventory(&config)
+}
+
+#[derive(Serialize)]
+struct RewriteResult {
+    config: Value,
+    rewritten: Vec<String>,
+}
+
+// The first time a key is needed without one configured, a random key is put in the OS keyring
+fn secret_key(conn: &Connection, secrets: &SecretState) -> Result<SecretKey, String> {
+    let mut cached = secrets.0.lock().map_err(|_| "Failed to acquire secrets lock".to_string())?;
+    if let Some(key) = *cached {
+        return Ok(key);
+    }
+
+    let key = match load_secret_key_info(conn).map_err(|e| e.to_string())? {
+        None => {
+            let key = random_key();
+            store_keyring_key(&key)?;
+            save_secret_key_info(conn, &SecretKeyInfo {
+                source: KeySource::Keyring.as_str().to_string(),
+                salt: None,
+                verifier: verifier(&key)?,
+            }).map_err(|e| e.to_string())?;
+            key
+        }
+        Some(info) => match KeySource::parse(&info.source)? {
+            KeySource::Keyring => {
+                let key = load_keyring_key()?.ok_or("The secrets key is missing from the OS keyring")?;
+                if !verify(&key, &info.verifier) {
+                    return Err("The secrets key in the OS keyring doesn't match this database".to_string());
+                }
+                key
+            }
+            KeySource::Passphrase => return Err("Secrets are locked, unlock them with the passphrase first".to_string()),
+        },
+    };
+    *cached = Some(key);
+    Ok(key)
+}
+
+// Config JSON for settings.db, with MysqlPass and the Discord URL encrypted
+// Secrets are never stored in plain text, so saving fails while no key can be reached
+fn sealed_config_json(conn: &Connection, secrets: &SecretState, config: &Value, config_id: Option<i64>) -> Result<String, String> {
+    let mut config = config.clone();
+    restore_locked_config_secrets(conn, &mut config, config_id)?;
+    if has_plain_secrets(&config, &CONFIG_SECRETS) {
+        let key = secret_key(conn, secrets).map_err(|e| format!("{}. Set a passphrase under Settings > Secrets to save secrets without the OS keyring", e))?;
+        seal_secrets(&mut config, &CONFIG_SECRETS, &key)?;
+    }
+    serde_json::to_string(&config).map_err(|e| e.to_string())
+}
+
+// Swaps the placeholders the editor got for locked secrets back to the saved config's sealed values
+fn restore_locked_config_secrets(conn: &Connection, config: &mut Value, config_id: Option<i64>) -> Result<(), String> {
+    if !has_locked_placeholders(config, &CONFIG_SECRETS) {
+        return Ok(());
+    }
+    let stored = match config_id {
+        Some(config_id) => load_config_by_id(conn, config_id)
+            .map_err(|e| e.to_string())?
+            .and_then(|saved| serde_json::from_str::<Value>(&saved.config).ok()),
+        None => None,
+    };
+    if !restore_locked_secrets(config, stored.as_ref(), &CONFIG_SECRETS) {
+        return Err("The config has locked secrets with no saved config to take them from, re-enter them before saving".to_string());
+    }
+    Ok(())
+}
+
+// Saved configs go to the editor with placeholders; the sealed values stay in settings.db
+fn saved_config_for_ui(mut saved: SavedConfig) -> Result<SavedConfig, String> {
+    if let Ok(mut config) = serde_json::from_str::<Value>(&saved.config) {
+        mask_sealed_secrets(&mut config, &CONFIG_SECRETS);
+        saved.config = serde_json::to_string(&config).map_err(|e| e.to_string())?;
+    }
+    Ok(saved)
+}
+
+fn open_config_secrets(conn: &Connection, secrets: &SecretState, config: &mut Value) -> Result<(), String> {
+    if has_locked_placeholders(config, &CONFIG_SECRETS) {
+        return Err("Secrets are locked, unlock them with the passphrase first".to_string());
+    }
+    if has_sealed_secrets(config, &CONFIG_SECRETS) {
+        open_secrets(config, &CONFIG_SECRETS, &secret_key(conn, secrets)?)?;
+    }
+    Ok(())
+}
+
+fn seal_source_password(conn: &Connection, secrets: &SecretState, source: &PermissionsSource) -> Result<String, String> {
+    let mut source = serde_json::to_value(source).map_err(|e| e.to_string())?;
+    if has_plain_secrets(&source, &SOURCE_SECRETS) {
+        seal_secrets(&mut source, &SOURCE_SECRETS, &secret_key(conn, secrets)?)?;
+    }
+    serde_json::to_string(&source).map_err(|e| e.to_string())
+}
+
+fn open_source_password(conn: &Connection, secrets: &SecretState, source: PermissionsSource) -> Result<PermissionsSource, String> {
+    let mut value = serde_json::to_value(&source).map_err(|e| e.to_string())?;
+    if !has_sealed_secrets(&value, &SOURCE_SECRETS) {
+        return Ok(source);
+    }
+    open_secrets(&mut value, &SOURCE_SECRETS, &secret_key(conn, secrets)?)?;
+    serde_json::from_value(value).map_err(|e| e.to_string())
+}
+
+// Re-encrypts every stored secret; with open_with unset only plain text secrets are touched
+fn reseal_stored_secrets(conn: &Connection, open_with: Option<&SecretKey>, seal_with: &SecretKey) -> Result<(), String> {
+    for saved in load_configs(conn).map_err(|e| e.to_string())? {
+        let mut config: Value = serde_json::from_str(&saved.config).map_err(|e| e.to_string())?;
+        if let Some(key) = open_with {
+            open_secrets(&mut config, &CONFIG_SECRETS, key).map_err(|e| format!("{} in \"{}\"", e, saved.name))?;
+        }
+        seal_secrets(&mut config, &CONFIG_SECRETS, seal_with)?;
+        let config = serde_json::to_string(&config).map_err(|e| e.to_string())?;
+        if config != saved.config {
+            update_config(conn, saved.id.unwrap_or_default(), &saved.name, &config, saved.custom_export_paths.as_ref()).map_err(|e| e.to_string())?;
+        }
+    }
+
+    if let Some(stored) = load_permissions_source(conn).map_err(|e| e.to_string())? {
+        let mut source: Value = serde_json::from_str(&stored).map_err(|e| e.to_string())?;
+        if let Some(key) = open_with {
+            open_secrets(&mut source, &SOURCE_SECRETS, key)?;
+        }
+        seal_secrets(&mut source, &SOURCE_SECRETS, seal_with)?;
+        save_permissions_source(conn, Some(&serde_json::to_string(&source).map_err(|e| e.to_string())?)).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+// Databases from before secrets were encrypted get sealed on the first start that can reach the key
+fn seal_plain_stored_secrets(conn: &Connection, secrets: &SecretState) -> Result<(), String> {
+    let configs = load_configs(conn).map_err(|e| e.to_string())?;
+    let plain_config = configs.iter().any(|saved| {
+        serde_json::from_str::<Value>(&saved.config).map_or(false, |config| has_plain_secrets(&config, &CONFIG_SECRETS))
+    });
+    let plain_source = load_permissions_source(conn)
+        .map_err(|e| e.to_string())?
+        .and_then(|stored| serde_json::from_str::<Value>(&stored).ok())
+        .map_or(false, |source| has_plain_secrets(&source, &SOURCE_SECRETS));
+
+    if plain_config || plain_source {
+        let key = secret_key(conn, secrets)?;
+        let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+        reseal_stored_secrets(&tx, None, &key)?;
+        tx.commit().map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct SecretsStatus {
+    source: Option<KeySource>,
+    unlocked: bool,
+    // Why the OS keyring can't be used, when secrets would be kept there
+    keyring_error: Option<String>,
+}
+
+#[tauri::command]
+fn get_secrets_status_command(state: tauri::State<AppState>, secrets: tauri::State<SecretState>) -> Result<SecretsStatus, String> {
+    let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
+    let source = load_secret_key_info(&conn)
+        .map_err(|e| e.to_string())?
+        .map(|info| KeySource::parse(&info.source))
+        .transpose()?;
+    let unlocked = secrets.0.lock().map_err(|_| "Failed to acquire secrets lock".to_string())?.is_some();
+    let keyring_error = match source {
+        Some(KeySource::Passphrase) => None,
+        _ => load_keyring_key().err(),
+    };
+    Ok(SecretsStatus { source, unlocked, keyring_error })
+}
+
+#[tauri::command]
+fn unlock_secrets_command(state: tauri::State<AppState>, secrets: tauri::State<SecretState>, passphrase: String) -> Result<(), String> {
+    let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
+    let info = load_secret_key_info(&conn)
+        .map_err(|e| e.to_string())?
+        .ok_or("No secrets passphrase has been set")?;
+    if KeySource::parse(&info.source)? != KeySource::Passphrase {
+        return Err("Secrets are kept in the OS keyring and don't need unlocking".to_string());
+    }
+
+    let key = derive_key(&passphrase, info.salt.as_deref().unwrap_or_default())?;
+    if !verify(&key, &info.verifier) {
+        return Err("Wrong passphrase".to_string());
+    }
+    *secrets.0.lock().map_err(|_| "Failed to acquire secrets lock".to_string())? = Some(key);
+    Ok(())
+}
+
+// Switches to a new keyring key or passphrase and re-encrypts every stored secret with it
+#[tauri::command]
+fn rotate_secrets_key_command(state: tauri::State<AppState>, secrets: tauri::State<SecretState>, source: KeySource, passphrase: Option<String>) -> Result<(), String> {
+    let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
+    // Without key info nothing has been sealed yet, so there is no old key to open with
+    let (old_key, previous_source) = match load_secret_key_info(&conn).map_err(|e| e.to_string())? {
+        Some(info) => (Some(secret_key(&conn, &secrets)?), Some(KeySource::parse(&info.source)?)),
+        None => (None, None),
+    };
+
+    let (new_key, salt) = match source {
+        KeySource::Keyring => (random_key(), None),
+        KeySource::Passphrase => {
+            let passphrase = passphrase
+                .filter(|passphrase| !passphrase.is_empty())
+                .ok_or("A passphrase is required")?;
+            let salt = new_salt();
+            (derive_key(&passphrase, &salt)?, Some(salt))
+        }
+    };
+
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+    reseal_stored_secrets(&tx, old_key.as_ref(), &new_key)?;
+    save_secret_key_info(&tx, &SecretKeyInfo {
+        source: source.as_str().to_string(),
+        salt,
+        verifier: verifier(&new_key)?,
+    }).map_err(|e| e.to_string())?;
+
+    if source == KeySource::Keyring {
+        store_keyring_key(&new_key)?;
+    }
+    if let Err(e) = tx.commit() {
+        // Put the old key back so the untouched database can still be read
+        if let (Some(KeySource::Keyring), Some(old_key)) = (previous_source, old_key) {
+            store_keyring_key(&old_key)?;
+        }
+        return Err(e.to_string());
+    }
+    if source == KeySource::Passphrase && previous_source == Some(KeySource::Keyring) {
+        delete_keyring_key()?;
+    }
+
+    *secrets.0.lock().map_err(|_| "Failed to acquire secrets lock".to_string())? = Some(new_key);
+    Ok(())
+}
+
+// Persists a rewritten config in a single update when it belongs to a saved config
+fn store_rewritten_config(state: &tauri::State<AppState>, secrets: &SecretState, config_id: Option<i64>, config: &Value) -> Result<(), String> {
+    if let Some(config_id) = config_id {
+        let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
+        let saved = load_config_by_id(&conn, config_id)
+            .map_err(|e| e.to_string())?
+            .ok_or("Saved configuration not found")?;
+        update_config(&conn, config_id, &saved.name, &sealed_config_json(&conn, secrets, config, Some(config_id))?, saved.custom_export_paths.as_ref()).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+#[tauri::command]
+fn rename_permission_group_command(state: tauri::State<AppState>, secrets: tauri::State<SecretState>, mut config: Value, config_id: Option<i64>, from: String, to: String) -> Result<RewriteResult, String> {
+    let rewritten = rename_group(&mut config, &from, &to)?;
+    store_rewritten_config(&state, &secrets, config_id, &config)?;
+    Ok(RewriteResult { config, rewritten })
+}
+
+#[tauri::command]
+fn rename_entry_key_command(state: tauri::State<AppState>, secrets: tauri::State<SecretState>, mut config: Value, config_id: Option<i64>, section: String, from: String, to: String) -> Result<RewriteResult, String> {
+    let rewritten = rename_entry_key(&mut config, &section, &from, &to)?;
+    store_rewritten_config(&state, &secrets, config_id, &config)?;
+    Ok(RewriteResult { config, rewritten })
+}
+
+#[tauri::command]
+fn validate_entry_keys_command(config: Value) -> Vec<KeyIssue> {
+    validate_entry_keys(&config)
+}
+
+#[tauri::command]
+fn reorder_entries_command(app_handle: tauri::AppHandle, mut config: Value, section: String, operation: OrderOperation) -> Result<Value, String> {
+    let categories = match operation {
+        OrderOperation::GroupByCategory => catalog_categories(&load_ark_data_from_app(&app_handle).data),
+        _ => HashMap::new(),
+    };
+    reorder_entries(&mut config, &section, &operation, &categories)?;
+    Ok(config)
+}
+
+#[tauri::command]
+fn normalize_blueprints_command(app_handle: tauri::AppHandle, mut config: Value) -> NormalizeReport {
+    let classes = class_index(&load_ark_data_from_app(&app_handle).data);
+    let (fixed, issues) = normalize_config_blueprints(&mut config, &classes);
+    NormalizeReport { config, fixed, issues }
+}
+
+#[tauri::command]
+fn validate_blueprints_command(app_handle: tauri::AppHandle, config: Value) -> Vec<BlueprintIssue> {
+    validate_config_blueprints(&config, &class_index(&load_ark_data_from_app(&app_handle).data))
+}
+
+#[tauri::command]
+fn list_catalog_beacons_command(app_handle: tauri::AppHandle) -> Vec<CatalogBeacon> {
+    catalog_beacons(&load_ark_data_from_app(&app_handle).data)
+}
+
+#[tauri::command]
+fn list_catalog_maps_command(app_handle: tauri::AppHandle) -> Vec<String> {
+    catalog_maps(&load_ark_data_from_app(&app_handle).data)
+}
+
+#[tauri::command]
+fn set_export_path_map_command(state: tauri::State<AppState>, path: String, map: Option<String>, drop_incompatible: Option<bool>) -> Result<(), String> {
+    let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
+    set_export_path_map(&conn, &path, map.as_deref(), drop_incompatible.unwrap_or(false)).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn get_export_path_map_command(state: tauri::State<AppState>, path: String) -> Result<Option<ExportPathMap>, String> {
+    let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
+    load_export_path_map(&conn, &path).map_err(|e| e.to_string())
+}
+
+// Checks against an explicit map, or the map stored for an export path
+#[tauri::command]
+fn validate_beacon_maps_command(app_handle: tauri::AppHandle, state: tauri::State<AppState>, config: Value, map: Option<String>, export_path: Option<String>) -> Result<Vec<BeaconMapIssue>, String> {
+    let map = match (map, export_path) {
+        (Some(map), _) => Some(map),
+        (None, Some(path)) => {
+            let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
+            load_export_path_map(&conn, &path).map_err(|e| e.to_string())?.map(|path_map| path_map.map)
+        }
+        (None, None) => None,
+    };
+    match map {
+        Some(map) => Ok(check_beacon_maps(&config, &load_ark_data_from_app(&app_handle).data, &map)),
+        None => Ok(Vec::new()),
+    }
+}
+
+#[tauri::command]
+fn validate_shop_entries_command(app_handle: tauri::AppHandle, config: Value) -> Vec<EntryIssue> {
+    let catalog = load_ark_data_from_app(&app_handle);
+    let blueprints = if catalog.is_usable() {
+        Some(CatalogBlueprints::from_catalog(&catalog.data))
+    } else {
+        None
+    };
+    validate_shop_entries(&config, blueprints.as_ref())
+}
+
+// Falls back to the built-in deny list until one has been saved
+fn command_deny_list(conn: &rusqlite::Connection) -> Result<Vec<String>, String> {
+    match load_command_deny_list(conn).map_err(|e| e.to_string())? {
+        Some(stored) => serde_json::from_str(&stored).map_err(|e| e.to_string()),
+        None => Ok(default_deny_list()),
+    }
+}
+
+#[tauri::command]
+fn get_command_deny_list_command(state: tauri::State<AppState>) -> Result<Vec<String>, String> {
+    let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
+    command_deny_list(&conn)
+}
+
+// Passing None restores the built-in list
+#[tauri::command]
+fn set_command_deny_list_command(state: tauri::State<AppState>, patterns: Option<Vec<String>>) -> Result<(), String> {
+    let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
+    let stored = patterns.map(|patterns| serde_json::to_string(&patterns)).transpose().map_err(|e| e.to_string())?;
+    save_command_deny_list(&conn, stored.as_deref()).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn analyze_commands_command(state: tauri::State<AppState>, config: Value) -> Result<Vec<CommandIssue>, String> {
+    let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
+    Ok(analyze_commands(&config, &command_deny_list(&conn)?))
+}
+
+fn load_saved_config_value(state: &tauri::State<AppState>, config_id: i64) -> Result<Value, String> {
+    let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
+    let saved = load_config_by_id(&conn, config_id)
+        .map_err(|e| e.to_string())?
+        .ok_or("Saved configuration not found")?;
+    serde_json::from_str(&saved.config).map_err(|e| e.to_string())
+}
+
+// Writes CSV for a .csv path and XLSX for anything else
+#[tauri::command]
+fn export_sheet_command(config: Value, file_path: String) -> Result<(), String> {
+    let path = PathBuf::from(&file_path);
+    let rows = sheet_rows(&config);
+    if path.extension().and_then(|ext| ext.to_str()).map_or(false, |ext| ext.eq_ignore_ascii_case("csv")) {
+        write_csv(&path, &rows)
+    } else {
+        write_xlsx(&path, &rows)
+    }
+}
+
+#[tauri::command]
+fn import_sheet_command(state: tauri::State<AppState>, config_id: i64, file_path: String) -> Result<SheetImport, String> {
+    let config = load_saved_config_value(&state, config_id)?;
+    let rows = read_sheet(Path::new(&file_path))?;
+    Ok(import_sheet(&config, &rows))
+}
+
+#[tauri::command]
+fn apply_sheet_diff_command(state: tauri::State<AppState>, secrets: tauri::State<SecretState>, config_id: i64, diff: SheetDiff) -> Result<Value, String> {
+    let mut config = load_saved_config_value(&state, config_id)?;
+    apply_sheet_diff(&mut config, &diff)?;
+    store_rewritten_config(&state, &secrets, Some(config_id), &config)?;
+    mask_sealed_secrets(&mut config, &CONFIG_SECRETS);
+    Ok(config)
+}
+
+fn unique_config_name(conn: &Connection, base: &str) -> Result<String, String> {
+    unique_name(base, |name| config_name_exists(conn, name)).map_err(|e| e.to_string())
+}
+
+// Shares a config with its credentials and webhook blanked, plus the custom catalog entries it uses
+#[tauri::command]
+fn export_share_bundle_command(app_handle: tauri::AppHandle, mut config: Value, source_name: String, file_path: String) -> Result<BundleMetadata, String> {
+    let redacted = redact_config(&mut config);
+    let active = load_ark_data_from_app(&app_handle);
+    let bundled = load_ark_data(&bundled_catalog_path(&app_handle)?).data;
+    let custom_catalog = custom_catalog_entries(&config, &active.data, &bundled);
+    let metadata = new_metadata(app_handle.package_info().version.to_string(), active.report.version, source_name, redacted);
+
+    let path = with_extension(file_path, BUNDLE_EXTENSION);
+    write_bundle(&path, &config, &metadata, &custom_catalog)?;
+    Ok(metadata)
+}
+
+#[derive(Serialize)]
+struct BundleImport {
+    config_id: i64,
+    name: String,
+    metadata: BundleMetadata,
+    // Custom catalog entries the config relies on, for the user to add to their catalog
+    custom_catalog: Value,
+}
+
+// Verifies the bundle's checksums and saves its config as a new saved config
+#[tauri::command]
+fn import_share_bundle_command(state: tauri::State<AppState>, secrets: tauri::State<SecretState>, file_path: String, name: Option<String>) -> Result<BundleImport, String> {
+    let bundle = read_bundle(Path::new(&file_path))?;
+    let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
+    let base = name
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or_else(|| bundle.metadata.source_name.clone());
+    let name = unique_config_name(&conn, &base)?;
+
+    let saved_config = SavedConfig {
+        id: None,
+        name: name.clone(),
+        config: sealed_config_json(&conn, &secrets, &bundle.config, None)?,
+        custom_export_paths: Some(Vec::new()),
+    };
+    let config_id = save_config(&conn, &saved_config).map_err(|e| e.to_string())?;
+
+    Ok(BundleImport {
+        config_id,
+        name,
+        metadata: bundle.metadata,
+        custom_catalog: bundle.custom_catalog,
+    })
+}
+
+fn with_extension(file_path: String, extension: &str) -> PathBuf {
+    let mut path = PathBuf::from(file_path);
+    if path.extension().is_none() {
+        path.set_extension(extension);
+    }
+    path
+}
+
+#[tauri::command]
+fn export_workspace_command(app_handle: tauri::AppHandle, state: tauri::State<AppState>, file_path: String) -> Result<String, String> {
+    let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
+    let workspace = collect_workspace(&conn, app_handle.package_info().version.to_string()).map_err(|e| e.to_string())?;
+    let path = with_extension(file_path, WORKSPACE_EXTENSION);
+    write_workspace(&path, &workspace, catalog_override_dir(&app_handle).as_deref())?;
+    Ok(path.to_string_lossy().into_owned())
+}
+
+// Archived secrets are re-encrypted with this machine's key. Ones sealed with a keyring key
+// that isn't here (or a passphrase that wasn't given) are cleared and reported.
+fn recover_workspace_secrets(conn: &Connection, secrets: &SecretState, workspace: &mut Workspace, passphrase: Option<&str>) -> Result<Vec<String>, String> {
+    let archive_key = match &workspace.secret_key {
+        Some(info) => match KeySource::parse(&info.source)? {
+            KeySource::Passphrase => match passphrase {
+                Some(passphrase) => {
+                    let key = derive_key(passphrase, info.salt.as_deref().unwrap_or_default())?;
+                    if !verify(&key, &info.verifier) {
+                        return Err("Wrong passphrase for the backup's secrets".to_string());
+                    }
+                    Some(key)
+                }
+                None => None,
+            },
+            KeySource::Keyring => load_keyring_key().ok().flatten().filter(|key| verify(key, &info.verifier)),
+        },
+        None => None,
+    };
+
+    let mut unrecoverable = Vec::new();
+    let mut local_key: Option<SecretKey> = None;
+    let mut recover = |value: &mut Value, pointers: &[&str], label: &str| -> Result<(), String> {
+        if has_sealed_secrets(value, pointers) {
+            match &archive_key {
+                Some(key) => open_secrets(value, pointers, key)?,
+                None => {
+                    clear_sealed_secrets(value, pointers);
+                    unrecoverable.push(label.to_string());
+                }
+            }
+        }
+        if has_plain_secrets(value, pointers) {
+            let key = match local_key {
+                Some(key) => key,
+                None => secret_key(conn, secrets)?,
+            };
+            local_key = Some(key);
+            seal_secrets(value, pointers, &key)?;
+        }
+        Ok(())
+    };
+
+    for saved in workspace.saved_configs.iter_mut() {
+        let mut config: Value = serde_json::from_str(&saved.config).map_err(|e| e.to_string())?;
+        recover(&mut config, &CONFIG_SECRETS, &saved.name)?;
+        saved.config = serde_json::to_string(&config).map_err(|e| e.to_string())?;
+    }
+    if let Some(stored) = workspace.permissions_source.as_mut() {
+        let mut source: Value = serde_json::from_str(stored).map_err(|e| e.to_string())?;
+        recover(&mut source, &SOURCE_SECRETS, "Permissions source")?;
+        *stored = serde_json::to_string(&source).map_err(|e| e.to_string())?;
+    }
+    Ok(unrecoverable)
+}
+
+// Replace mode backs up settings.db first
+#[tauri::command]
+fn restore_workspace_command(app_handle: tauri::AppHandle, state: tauri::State<AppState>, secrets: tauri::State<SecretState>, file_path: String, mode: RestoreMode, passphrase: Option<String>) -> Result<RestoreReport, String> {
+    let mut archive = read_workspace(Path::new(&file_path))?;
+    let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
+    let mut report = RestoreReport {
+        unrecoverable_secrets: recover_workspace_secrets(&conn, &secrets, &mut archive.workspace, passphrase.as_deref())?,
+        ..Default::default()
+    };
+
+    if mode == RestoreMode::Replace {
+        let version = migrations::user_version(&conn).map_err(|e| e.to_string())?;
+        let backup = migrations::backup_database(&conn, &get_database_path(&app_handle), version).map_err(|e| e.to_string())?;
+        report.backup = Some(backup.to_string_lossy().into_owned());
+    }
+
+    // Catalogs are staged beside the catalogs folder and only moved in once the database
+    // has committed, so a failure on either side before that leaves both untouched
+    let staged = match catalog_override_dir(&app_handle) {
+        Some(dir) => Some(stage_catalogs(&dir, &archive.catalogs, mode, &mut report).map_err(|e| format!("Failed to restore catalogs: {}", e))?),
+        None => None,
+    };
+    let tx = conn.unchecked_transaction().map_err(|e| e.to_string())?;
+    restore_workspace(&tx, &archive.workspace, mode, &mut report).map_err(|e| e.to_string())?;
+    tx.commit().map_err(|e| e.to_string())?;
+    if let Some(staged) = staged {
+        let backup = staged.install().map_err(|e| format!("Workspace restored but its catalogs couldn't be moved into place: {}", e))?;
+        report.catalog_backup = backup.map(|backup| backup.to_string_lossy().into_owned());
+    }
+    Ok(report)
+}
+
+#[tauri::command]
+fn get_backup_schedule_command(state: tauri::State<AppState>) -> Result<Option<BackupSchedule>, String> {
+    let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
+    load_backup_schedule(&conn).map_err(|e| e.to_string())
+}
+
+// None turns scheduled backups off
+#[tauri::command]
+fn set_backup_schedule_command(state: tauri::State<AppState>, schedule: Option<BackupSchedule>) -> Result<(), String> {
+    let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
+    let schedule = match schedule {
+        Some(mut schedule) => {
+            if schedule.directory.trim().is_empty() {
+                return Err("A backup folder is required".to_string());
+            }
+            if schedule.interval_hours < 1 || schedule.retention < 1 {
+                return Err("Interval and retention must be at least 1".to_string());
+            }
+            schedule.last_backup = load_backup_schedule(&conn).map_err(|e| e.to_string())?.and_then(|stored| stored.last_backup);
+            Some(schedule)
+        }
+        None => None,
+    };
+    save_backup_schedule(&conn, schedule.as_ref()).map_err(|e| e.to_string())
+}
+
+// Writes a workspace backup when the schedule is due and prunes old ones past the retention count
+fn run_scheduled_backup(app_handle: &tauri::AppHandle) -> Result<Option<PathBuf>, String> {
+    let state = app_handle.state::<AppState>();
+    let now = Utc::now();
+    // The database is only locked while reading, not while the archive is written
+    let (schedule, workspace) = {
+        let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
+        let schedule = match load_backup_schedule(&conn).map_err(|e| e.to_string())? {
+            Some(schedule) => schedule,
+            None => return Ok(None),
+        };
+        if schedule.last_backup.map_or(false, |last| now - last < chrono::Duration::hours(schedule.interval_hours)) {
+            return Ok(None);
+        }
+        let workspace = collect_workspace(&conn, app_handle.package_info().version.to_string()).map_err(|e| e.to_string())?;
+        (schedule, workspace)
+    };
+
+    let dir = PathBuf::from(&schedule.directory);
+    fs::create_dir_all(&dir).map_err(|e| format!("Failed to create backup folder: {}", e))?;
+    let path = dir.join(scheduled_backup_name(now));
+    write_workspace(&path, &workspace, catalog_override_dir(app_handle).as_deref())?;
+    {
+        let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
+        set_last_backup(&conn, now).map_err(|e| e.to_string())?;
+    }
+    prune_backups(&dir, schedule.retention.max(1) as usize).map_err(|e| format!("Failed to remove old backups: {}", e))?;
+    Ok(Some(path))
+}
+
+// Renders the player-facing price list, writing it to file_path when one is given
+#[tauri::command]
+fn export_catalog_command(app_handle: tauri::AppHandle, config: Value, format: CatalogFormat, title: Option<String>, templates: Option<TemplateOverrides>, file_path: Option<String>) -> Result<RenderedCatalog, String> {
+    let templates = CatalogTemplates::with_overrides(format, templates.unwrap_or_default());
+    let title = title.unwrap_or_else(|| "Shop".to_string());
+    let rendered = export_catalog(&config, &load_ark_data_from_app(&app_handle).data, &title, format, &templates);
+    if let Some(file_path) = file_path {
+        fs::write(&file_path, &rendered.content).map_err(|e| format!("Failed to write file: {}", e))?;
+    }
+    Ok(rendered)
+}
+
+#[tauri::command]
+fn get_catalog_templates_command(format: CatalogFormat) -> CatalogTemplates {
+    CatalogTemplates::defaults(format)
+}
+
+// Writes the schema to file_path when one is given, e.g. next to config.json for VS Code
+#[tauri::command]
+fn generate_config_schema_command(app_handle: tauri::AppHandle, include_catalog: Option<bool>, file_path: Option<String>) -> Result<Value, String> {
+    let enums = if include_catalog.unwrap_or(false) {
+        Some(CatalogEnums::from_catalog(&load_ark_data_from_app(&app_handle).data))
+    } else {
+        None
+    };
+    let schema = config_schema(enums.as_ref());
+    if let Some(file_path) = file_path {
+        fs::write(&file_path, serde_json::to_string_pretty(&schema).unwrap())
+            .map_err(|e| format!("Failed to write file: {}", e))?;
+    }
+    Ok(schema)
+}
+
+#[tauri::command]
+fn preview_shop_command(config: Value) -> ShopPreview {
+    preview_shop(&config)
+}
+
+#[tauri::command]
+fn set_permissions_source_command(state: tauri::State<AppState>, secrets: tauri::State<SecretState>, source: Option<PermissionsSource>) -> Result<(), String> {
+    let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
+    let source = source
+        .map(|source| seal_source_password(&conn, &secrets, &source))
+        .transpose()?;
+    save_permissions_source(&conn, source.as_deref()).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn get_permissions_source_command(state: tauri::State<AppState>) -> Result<Option<PermissionsSource>, String> {
+    let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
+    match load_permissions_source(&conn).map_err(|e| e.to_string())? {
+        Some(source) => serde_json::from_str(&source).map(Some).map_err(|e| e.to_string()),
+        None => Ok(None),
+    }
+}
+
+// An explicit source wins, then the config's own Mysql section if asked for, then the stored source
+// Encrypted passwords are decrypted here, right before connecting
+fn resolve_permissions_source(state: &tauri::State<AppState>, secrets: &SecretState, config: Option<&Value>, config_id: Option<i64>, source: Option<PermissionsSource>, use_config_mysql: bool) -> Result<PermissionsSource, String> {
+    let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
+    if let Some(source) = source {
+        return open_source_password(&conn, secrets, source);
+    }
+    if use_config_mysql {
+        let mut config = config.ok_or("A config is required to use its Mysql settings")?.clone();
+        restore_locked_config_secrets(&conn, &mut config, config_id)?;
+        open_config_secrets(&conn, secrets, &mut config)?;
+        return PermissionsSource::from_config_mysql(&config, None);
+    }
+
+    let stored = load_permissions_source(&conn)
+        .map_err(|e| e.to_string())?
+        .ok_or("No Permissions database has been configured")?;
+    open_source_password(&conn, secrets, serde_json::from_str(&stored).map_err(|e| e.to_string())?)
+}
+
+#[tauri::command]
+async fn load_server_groups_command(state: tauri::State<'_, AppState>, secrets: tauri::State<'_, SecretState>, source: Option<PermissionsSource>) -> Result<Vec<String>, String> {
+    let source = resolve_permissions_source(&state, &secrets, None, None, source, false)?;
+    task::spawn_blocking(move || read_permission_groups(&source))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))?
+}
+
+#[tauri::command]
+async fn validate_server_groups_command(state: tauri::State<'_, AppState>, secrets: tauri::State<'_, SecretState>, config: Value, config_id: Option<i64>, source: Option<PermissionsSource>, use_config_mysql: Option<bool>) -> Result<Vec<UnknownGroup>, String> {
+    let source = resolve_permissions_source(&state, &secrets, Some(&config), config_id, source, use_config_mysql.unwrap_or(false))?;
+    let groups = task::spawn_blocking(move || read_permission_groups(&source))
+        .await
+        .map_err(|e| format!("Task join error: {}", e))??;
+    Ok(find_unknown_groups(&config, &groups))
+}
+
+#[tauri::command]
+fn player_eligibility_command(config: Value, player: PlayerProfile) -> EligibilityReport {
+    eligibility_report(&config, &player)
+}
+
+#[tauri::command]
+fn simulate_purchases_command(config: Value, scenario: Scenario) -> ScenarioResult {
+    run_scenario(&config, scenario)
+}
+
+// Without an explicit group list, falls back to the configured Permissions database if there is one
+#[tauri::command]
+async fn unreachable_entries_command(state: tauri::State<'_, AppState>, secrets: tauri::State<'_, SecretState>, config: Value, known_groups: Option<Vec<String>>) -> Result<Vec<UnreachableEntry>, String> {
+    let known_groups = match known_groups {
+        Some(groups) => Some(groups),
+        None => {
+            let stored = {
+                let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
+                load_permissions_source(&conn).map_err(|e| e.to_string())?
+            };
+            match stored {
+                Some(_) => {
+                    let source = resolve_permissions_source(&state, &secrets, None, None, None, false)?;
+                    let groups = task::spawn_blocking(move || read_permission_groups(&source))
+                        .await
+                        .map_err(|e| format!("Task join error: {}", e))??;
+                    Some(groups)
+                }
+                None => None,
+            }
+        }
+    };
+    Ok(unreachable_entries(&config, known_groups.as_deref()))
+}
+
 #[derive(Serialize)]
 struct ExportResult {
     file_path: String,
     file_existed: bool,
+    // Beacon entries left out because they don't exist on the path's map
+    dropped_entries: Vec<String>,
+}
+
+// Commands on the deny list only go out once the user has acknowledged them
+fn ensure_commands_acknowledged(state: &AppState, config: &Value, acknowledged: Option<bool>) -> Result<(), String> {
+    if acknowledged.unwrap_or(false) {
+        return Ok(());
+    }
+    let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
+    let denied = denied_entries(config, &command_deny_list(&conn)?);
+    if denied.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("These command entries run denied commands and must be acknowledged before export: {}", denied.join(", ")))
+    }
+}
+
+// Applies message packs and, when the export path asks for it, drops beacons its map doesn't have.
+// Secrets are only decrypted into the exported copy.
+fn prepare_export(app_handle: &tauri::AppHandle, state: &AppState, secrets: &SecretState, config: &Value, config_id: Option<i64>, path: &str) -> Result<(Value, Vec<String>), String> {
+    let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
+    let mut exported = config_for_export(&conn, config, config_id, path)?;
+    restore_locked_config_secrets(&conn, &mut exported, config_id)?;
+    open_config_secrets(&conn, secrets, &mut exported)?;
+    let dropped = match load_export_path_map(&conn, path).map_err(|e| e.to_string())? {
+        Some(path_map) if path_map.drop_incompatible => {
+            drop_incompatible_beacons(&mut exported, &load_ark_data_from_app(app_handle).data, &path_map.map)
+        }
+        _ => Vec::new(),
+    };
+    Ok((exported, dropped))
 }
 
 #[tauri::command]
-async fn export_config(config: Value, export_paths: Vec<String>) -> Result<Vec<ExportResult>, String> {
+async fn export_config(app_handle: tauri::AppHandle, state: tauri::State<'_, AppState>, secrets: tauri::State<'_, SecretState>, config: Value, export_paths: Vec<String>, config_id: Option<i64>, acknowledge_commands: Option<bool>) -> Result<Vec<ExportResult>, String> {
+    ensure_commands_acknowledged(&state, &config, acknowledge_commands)?;
     let mut results = Vec::new();
 
     for path in export_paths {
         let file_path = PathBuf::from(&path).join("config.json");
         let file_exists = Path::new(&file_path).exists();
+        let mut dropped_entries = Vec::new();
 
         if !file_exists {
-            fs::write(&file_path, serde_json::to_string_pretty(&config).unwrap())
+            let (exported, dropped) = prepare_export(&app_handle, &state, &secrets, &config, config_id, &path)?;
+            fs::write(&file_path, serde_json::to_string_pretty(&exported).unwrap())
                 .map_err(|e| format!("Failed to write file: {}", e))?;
+            dropped_entries = dropped;
         }
 
         results.push(ExportResult {
             file_path: file_path.to_string_lossy().into_owned(),
             file_existed: file_exists,
+            dropped_entries,
         });
     }
 
@@ -99,11 +1084,13 @@ async fn export_config(config: Value, export_paths: Vec<String>) -> Result<Vec<E
 }
 
 #[tauri::command]
-fn force_export_config(config: Value, file_path: String) -> Result<(), String> {
+fn force_export_config(app_handle: tauri::AppHandle, state: tauri::State<AppState>, secrets: tauri::State<SecretState>, config: Value, file_path: String, config_id: Option<i64>, acknowledge_commands: Option<bool>) -> Result<Vec<String>, String> {
+    ensure_commands_acknowledged(&state, &config, acknowledge_commands)?;
+    let (exported, dropped) = prepare_export(&app_handle, &state, &secrets, &config, config_id, &file_path)?;
     let path = PathBuf::from(&file_path).join("config.json");
-    fs::write(&path, serde_json::to_string_pretty(&config).unwrap())
+    fs::write(&path, serde_json::to_string_pretty(&exported).unwrap())
         .map_err(|e| format!("Failed to write file: {}", e))?;
-    Ok(())
+    Ok(dropped)
 }
 
 #[tauri::command]
@@ -144,12 +1131,13 @@ fn open_file_location(path: String) -> Result<(), String> {
     Ok(())
 }
 
+// source_id is the saved config a new one was copied from, whose sealed secrets stand in for its placeholders
 #[tauri::command]
-fn save_config_command(state: tauri::State<AppState>, id: Option<i64>, name: String, config: Value, custom_export_paths: Option<Vec<String>>) -> Result<i64, String> {
+fn save_config_command(state: tauri::State<AppState>, secrets: tauri::State<SecretState>, id: Option<i64>, name: String, config: Value, custom_export_paths: Option<Vec<String>>, source_id: Option<i64>) -> Result<i64, String> {
     let conn = state.0.lock().unwrap();
     
     if let Some(id) = id {
-        update_config(&conn, id, &name, &serde_json::to_string(&config).map_err(|e| e.to_string())?, custom_export_paths.as_ref()).map_err(|e| e.to_string())?;
+        update_config(&conn, id, &name, &sealed_config_json(&conn, &secrets, &config, Some(id))?, custom_export_paths.as_ref()).map_err(|e| e.to_string())?;
         Ok(id)
     } else {
         if config_name_exists(&conn, &name).map_err(|e| e.to_string())? {
@@ -159,7 +1147,7 @@ fn save_config_command(state: tauri::State<AppState>, id: Option<i64>, name: Str
         let saved_config = SavedConfig {
             id: None,
             name,
-            config: serde_json::to_string(&config).map_err(|e| e.to_string())?,
+            config: sealed_config_json(&conn, &secrets, &config, source_id)?,
             custom_export_paths,
         };
         save_config(&conn, &saved_config).map_err(|e| e.to_string())
@@ -169,7 +1157,23 @@ fn save_config_command(state: tauri::State<AppState>, id: Option<i64>, name: Str
 #[tauri::command]
 fn load_configs_command(state: tauri::State<AppState>) -> Result<Vec<SavedConfig>, String> {
     let conn = state.0.lock().unwrap();
-    load_configs(&conn).map_err(|e| e.to_string())
+    load_configs(&conn)
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(saved_config_for_ui)
+        .collect()
+}
+
+// The one place a saved config's secrets are decrypted for the editor, on the user's request
+#[tauri::command]
+fn reveal_config_secrets_command(state: tauri::State<AppState>, secrets: tauri::State<SecretState>, config_id: i64) -> Result<Value, String> {
+    let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
+    let saved = load_config_by_id(&conn, config_id)
+        .map_err(|e| e.to_string())?
+        .ok_or("Saved configuration not found")?;
+    let mut config: Value = serde_json::from_str(&saved.config).map_err(|e| e.to_string())?;
+    open_config_secrets(&conn, &secrets, &mut config)?;
+    Ok(config)
 }
 
 #[tauri::command]
@@ -178,6 +1182,103 @@ fn delete_config_command(state: tauri::State<AppState>, id: i64) -> Result<(), S
     delete_config(&conn, id).map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+fn load_message_packs_command(state: tauri::State<AppState>) -> Result<Vec<MessagePack>, String> {
+    let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
+    load_message_packs(&conn).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn save_message_pack_command(state: tauri::State<AppState>, id: Option<i64>, name: String, messages: Value, is_default: Option<bool>) -> Result<i64, String> {
+    let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
+    if !messages.is_object() {
+        return Err("Messages must be a JSON object".to_string());
+    }
+    let messages = serde_json::to_string(&messages).map_err(|e| e.to_string())?;
+
+    let id = if let Some(id) = id {
+        update_message_pack(&conn, id, &name, &messages).map_err(|e| e.to_string())?;
+        id
+    } else {
+        if message_pack_name_exists(&conn, &name).map_err(|e| e.to_string())? {
+            return Err("A message pack with this name already exists".to_string());
+        }
+        let pack = MessagePack { id: None, name, messages, is_default: false };
+        save_message_pack(&conn, &pack).map_err(|e| e.to_string())?
+    };
+
+    if is_default == Some(true) {
+        set_default_message_pack(&conn, id).map_err(|e| e.to_string())?;
+    }
+    Ok(id)
+}
+
+#[tauri::command]
+fn delete_message_pack_command(state: tauri::State<AppState>, id: i64) -> Result<(), String> {
+    let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
+    delete_message_pack(&conn, id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn set_default_message_pack_command(state: tauri::State<AppState>, id: i64) -> Result<(), String> {
+    let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
+    set_default_message_pack(&conn, id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn set_config_message_pack_command(state: tauri::State<AppState>, config_id: i64, pack_id: Option<i64>) -> Result<(), String> {
+    let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
+    set_config_message_pack(&conn, config_id, pack_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn get_config_message_pack_command(state: tauri::State<AppState>, config_id: i64) -> Result<Option<i64>, String> {
+    let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
+    load_config_message_pack(&conn, config_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn set_export_path_message_pack_command(state: tauri::State<AppState>, path: String, pack_id: Option<i64>) -> Result<(), String> {
+    let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
+    set_export_path_message_pack(&conn, &path, pack_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn get_export_path_message_pack_command(state: tauri::State<AppState>, path: String) -> Result<Option<i64>, String> {
+    let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
+    load_export_path_message_pack(&conn, &path).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn import_message_pack_command(state: tauri::State<AppState>, file_path: String, name: Option<String>) -> Result<i64, String> {
+    let (file_name, messages) = read_message_pack_file(Path::new(&file_path))?;
+    let name = name
+        .or(file_name)
+        .or_else(|| Path::new(&file_path).file_stem().map(|stem| stem.to_string_lossy().into_owned()))
+        .ok_or("Message pack needs a name")?;
+
+    let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
+    if message_pack_name_exists(&conn, &name).map_err(|e| e.to_string())? {
+        return Err("A message pack with this name already exists".to_string());
+    }
+    let pack = MessagePack {
+        id: None,
+        name,
+        messages: serde_json::to_string(&messages).map_err(|e| e.to_string())?,
+        is_default: false,
+    };
+    save_message_pack(&conn, &pack).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn export_message_pack_command(state: tauri::State<AppState>, id: i64, file_path: String) -> Result<(), String> {
+    let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
+    let pack = load_message_pack_by_id(&conn, id)
+        .map_err(|e| e.to_string())?
+        .ok_or("Message pack not found")?;
+    write_message_pack_file(Path::new(&file_path), &pack)
+}
+
 #[tauri::command]
 fn get_hwid() -> String {
     hwid::generate_hwid()
@@ -282,14 +1383,14 @@ async fn check_license_on_startup(state: tauri::State<'_, AppState>, license_sta
 }
 
 #[tauri::command]
-async fn auto_save_config(config: Value, config_id: i64, state: tauri::State<'_, AppState>) -> Result<(), String> {
+async fn auto_save_config(config: Value, config_id: i64, state: tauri::State<'_, AppState>, secrets: tauri::State<'_, SecretState>) -> Result<(), String> {
     log_to_file(&format!("Auto-save triggered for config_id: {}", config_id));
     let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
     let current_config = load_config_by_id(&conn, config_id).map_err(|e| e.to_string())?;
     
     if let Some(current_config) = current_config {
         log_to_file(&format!("Updating config: {}", current_config.name));
-        update_config(&conn, current_config.id.unwrap(), &current_config.name, &serde_json::to_string(&config).map_err(|e| e.to_string())?, current_config.custom_export_paths.as_ref()).map_err(|e| e.to_string())?;
+        update_config(&conn, current_config.id.unwrap(), &current_config.name, &sealed_config_json(&conn, &secrets, &config, current_config.id)?, current_config.custom_export_paths.as_ref()).map_err(|e| e.to_string())?;
         log_to_file("Config updated successfully");
         Ok(())
     } else {
@@ -301,7 +1402,10 @@ async fn auto_save_config(config: Value, config_id: i64, state: tauri::State<'_,
 #[tauri::command]
 fn get_current_config(state: tauri::State<AppState>) -> Result<Option<SavedConfig>, String> {
     let conn = state.0.lock().map_err(|_| "Failed to acquire database lock".to_string())?;
-    load_current_config(&conn).map_err(|e| e.to_string())
+    load_current_config(&conn)
+        .map_err(|e| e.to_string())?
+        .map(saved_config_for_ui)
+        .transpose()
 }
 
 #[tauri::command]
@@ -310,7 +1414,37 @@ fn update_config_export_paths_command(state: tauri::State<AppState>, config_id:
     update_config_export_paths(&conn, config_id, paths).map_err(|e| e.to_string())
 }
 
+// Release builds on Windows have no console of their own, so print to the one the app was started from
+#[cfg(target_os = "windows")]
+fn attach_parent_console() {
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn attach_parent_console() {}
+
 fn main() {
+    // `--simulate <config.json> <scenario.json>` replays a purchase scenario and prints the results
+    let args: Vec<String> = env::args().collect();
+    if args.len() == 4 && args[1] == "--simulate" {
+        attach_parent_console();
+        match run_scenario_files(Path::new(&args[2]), Path::new(&args[3])) {
+            Ok(result) => println!("{}", serde_json::to_string_pretty(&result).unwrap_or_default()),
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
     std::panic::set_hook(Box::new(|panic_info| {
         if let Some(location) = panic_info.location() {
             log_to_file(&format!("Panic occurred in file '{}' at line {}", location.file(), location.line()));
@@ -329,9 +1463,35 @@ fn main() {
             .setup(|app| {
                 let app_handle = app.handle();
                 let db_path = get_database_path(&app_handle);
-                let conn = initialize_db(&db_path).expect("Failed to initialize database");
+                match migrate_database_location(&app_handle, &db_path) {
+                    Ok(Some(from)) => log_to_file(&format!("Copied settings.db from {} to {}", from.display(), db_path.display())),
+                    Ok(None) => {}
+                    Err(e) => log_to_file(&format!("Failed to move settings.db to {}: {}", db_path.display(), e)),
+                }
+                let (conn, backup) = initialize_db(&db_path).expect("Failed to initialize database");
+                if let Some(backup) = backup {
+                    log_to_file(&format!("Backed up settings.db to {} before migrating", backup.display()));
+                }
                 app.manage(AppState(Mutex::new(conn)));
 
+                {
+                    let state = app.state::<AppState>();
+                    let conn = state.0.lock().unwrap();
+                    if let Err(e) = seal_plain_stored_secrets(&conn, &app.state::<SecretState>()) {
+                        log_to_file(&format!("Stored secrets left unencrypted: {}", e));
+                    }
+                }
+
+                let backup_handle = app_handle.clone();
+                std::thread::spawn(move || loop {
+                    match run_scheduled_backup(&backup_handle) {
+                        Ok(Some(path)) => log_to_file(&format!("Workspace backed up to {}", path.display())),
+                        Ok(None) => {}
+                        Err(e) => log_to_file(&format!("Scheduled workspace backup failed: {}", e)),
+                    }
+                    std::thread::sleep(BACKUP_CHECK_INTERVAL);
+                });
+
                 #[cfg(not(debug_assertions))]
                 {
                     use tauri::Manager;
@@ -370,16 +1530,77 @@ fn main() {
                 Ok(())
             })
             .manage(LicenseState(Mutex::new(false)))
+            .manage(SecretState(Mutex::new(None)))
             .invoke_handler(tauri::generate_handler![
                 save_settings_command,
                 load_settings_command,
+                get_database_location_command,
+                set_database_override_command,
                 read_ark_data_command,
+                read_ark_data_report_command,
+                list_catalog_sources_command,
+                diff_catalogs_command,
+                catalog_impact_report_command,
+                preview_message_command,
+                validate_messages_markup_command,
+                get_message_schema_command,
+                validate_message_placeholders_command,
+                permission_group_inventory_command,
+                rename_permission_group_command,
+                rename_entry_key_command,
+                validate_entry_keys_command,
+                reorder_entries_command,
+                preview_shop_command,
+                set_permissions_source_command,
+                get_permissions_source_command,
+                load_server_groups_command,
+                validate_server_groups_command,
+                player_eligibility_command,
+                unreachable_entries_command,
+                simulate_purchases_command,
+                normalize_blueprints_command,
+                validate_blueprints_command,
+                list_catalog_beacons_command,
+                list_catalog_maps_command,
+                set_export_path_map_command,
+                get_export_path_map_command,
+                validate_beacon_maps_command,
+                validate_shop_entries_command,
+                get_command_deny_list_command,
+                set_command_deny_list_command,
+                analyze_commands_command,
+                export_sheet_command,
+                import_sheet_command,
+                apply_sheet_diff_command,
+                export_catalog_command,
+                get_catalog_templates_command,
+                generate_config_schema_command,
+                get_secrets_status_command,
+                unlock_secrets_command,
+                rotate_secrets_key_command,
+                reveal_config_secrets_command,
+                export_share_bundle_command,
+                import_share_bundle_command,
+                export_workspace_command,
+                restore_workspace_command,
+                get_backup_schedule_command,
+                set_backup_schedule_command,
                 export_config,
                 force_export_config,
                 open_file_location,
                 save_config_command,
                 load_configs_command,
                 delete_config_command,
+                load_message_packs_command,
+                save_message_pack_command,
+                delete_message_pack_command,
+                set_default_message_pack_command,
+                set_config_message_pack_command,
+                get_config_message_pack_command,
+                set_export_path_message_pack_command,
+                get_export_path_message_pack_command,
+                import_message_pack_command,
+                export_message_pack_command,
                 get_hwid,
                 validate_license,
                 get_license_state,
@@ -0,0 +1,94 @@
+// src-tauri/src/message_packs.rs
+
+use std::fs;
+use std::path::Path;
+use rusqlite::Connection;
+use serde_json::{json, Map, Value};
+use crate::db::{load_config_message_pack, load_default_message_pack, load_export_path_message_pack, load_message_pack_by_id, MessagePack};
+
+pub fn parse_pack_messages(messages: &str) -> Result<Map<String, Value>, String> {
+    match serde_json::from_str(messages).map_err(|e| format!("Error parsing message pack: {}", e))? {
+        Value::Object(messages) => Ok(messages),
+        _ => Err("Message pack must be a JSON object".to_string()),
+    }
+}
+
+// Overlays the selected pack on the default pack on the config's own Messages,
+// so keys missing from a pack fall back to the next layer down
+pub fn apply_message_packs(config: &mut Value, default_pack: Option<&MessagePack>, pack: Option<&MessagePack>) -> Result<(), String> {
+    if default_pack.is_none() && pack.is_none() {
+        return Ok(());
+    }
+
+    let root = config.as_object_mut().ok_or("Config must be a JSON object")?;
+    let messages = root
+        .entry("Messages")
+        .or_insert_with(|| Value::Object(Map::new()));
+    if !messages.is_object() {
+        *messages = Value::Object(Map::new());
+    }
+
+    if let Some(messages) = messages.as_object_mut() {
+        for layer in [default_pack, pack].iter().flatten() {
+            for (key, value) in parse_pack_messages(&layer.messages)? {
+                messages.insert(key, value);
+            }
+        }
+    }
+    Ok(())
+}
+
+// A server's pack wins over the saved config's pack
+pub fn resolve_message_pack(conn: &Connection, config_id: Option<i64>, export_path: &str) -> Result<Option<MessagePack>, String> {
+    let mut pack_id = load_export_path_message_pack(conn, export_path).map_err(|e| e.to_string())?;
+    if pack_id.is_none() {
+        if let Some(config_id) = config_id {
+            pack_id = load_config_message_pack(conn, config_id).map_err(|e| e.to_string())?;
+        }
+    }
+
+    match pack_id {
+        Some(id) => load_message_pack_by_id(conn, id).map_err(|e| e.to_string()),
+        None => Ok(None),
+    }
+}
+
+pub fn config_for_export(conn: &Connection, config: &Value, config_id: Option<i64>, export_path: &str) -> Result<Value, String> {
+    let pack = resolve_message_pack(conn, config_id, export_path)?;
+    if pack.is_none() {
+        return Ok(config.clone());
+    }
+
+    let default_pack = load_default_message_pack(conn).map_err(|e| e.to_string())?;
+    let mut exported = config.clone();
+    apply_message_packs(&mut exported, default_pack.as_ref(), pack.as_ref())?;
+    Ok(exported)
+}
+
+// Accepts either {"Name": ..., "Messages": {...}} or a bare Messages object
+pub fn read_message_pack_file(path: &Path) -> Result<(Option<String>, Map<String, Value>), String> {
+    let data = fs::read_to_string(path).map_err(|e| format!("Error reading file: {}", e))?;
+    let mut parsed = match serde_json::from_str(&data).map_err(|e| format!("Error parsing JSON: {}", e))? {
+        Value::Object(parsed) => parsed,
+        _ => return Err("Message pack file must contain a JSON object".to_string()),
+    };
+
+    match parsed.remove("Messages") {
+        Some(Value::Object(messages)) => {
+            let name = parsed.get("Name").and_then(Value::as_str).map(str::to_string);
+            Ok((name, messages))
+        }
+        Some(_) => Err("\"Messages\" must be a JSON object".to_string()),
+        None => Ok((None, parsed)),
+    }
+}
+
+pub fn write_message_pack_file(path: &Path, pack: &MessagePack) -> Result<(), String> {
+    let messages = parse_pack_messages(&pack.messages)?;
+    let file = json!({
+        "Name": pack.name,
+        "Messages": messages,
+    });
+    fs::write(path, serde_json::to_string_pretty(&file).map_err(|e| e.to_string())?)
+        .map_err(|e| format!("Failed to write file: {}", e))
+}
@@ -0,0 +1,206 @@
+// src-tauri/src/message_schema.rs
+
+use std::collections::{BTreeMap, BTreeSet};
+use serde::Serialize;
+use serde_json::Value;
+use crate::ark_data::IssueSeverity;
+
+// The plugin's stock config, which defines every Messages key it reads
+const DEFAULT_CONFIG: &str = include_str!("../config.json");
+
+#[derive(Serialize, Debug, Clone)]
+pub struct MessageSchema {
+    pub key: String,
+    // Placeholder indices the plugin fills in, e.g. [0, 1] for "{0} ... {1}"
+    pub placeholders: Vec<u32>,
+    pub default_text: String,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PlaceholderProblem {
+    Missing,
+    Extra,
+    OutOfRange,
+    Malformed,
+    UnknownKey,
+}
+
+#[derive(Serialize, Debug)]
+pub struct PlaceholderIssue {
+    pub key: String,
+    pub severity: IssueSeverity,
+    pub problem: PlaceholderProblem,
+    pub placeholder: Option<String>,
+    pub message: String,
+}
+
+pub fn default_config() -> Value {
+    serde_json::from_str(DEFAULT_CONFIG).expect("bundled default config.json is valid JSON")
+}
+
+// Splits a message into the numeric placeholder indices it uses and any
+// brace groups that aren't valid positional placeholders
+pub fn scan_placeholders(message: &str) -> (Vec<u32>, Vec<String>) {
+    let mut indices = Vec::new();
+    let mut malformed = Vec::new();
+    let mut pos = 0;
+
+    while let Some(found) = message[pos..].find('{') {
+        let start = pos + found;
+        match message[start..].find('}') {
+            Some(len) => {
+                let inner = &message[start + 1..start + len];
+                match inner.parse::<u32>() {
+                    Ok(index) if !inner.starts_with('+') => indices.push(index),
+                    _ => malformed.push(message[start..=start + len].to_string()),
+                }
+                pos = start + len + 1;
+            }
+            None => {
+                malformed.push(message[start..].to_string());
+                break;
+            }
+        }
+    }
+
+    (indices, malformed)
+}
+
+// Fills {0}, {1}, ... the way the plugin's FString::Format does, leaving
+// placeholders without a matching argument untouched
+pub fn format_placeholders(template: &str, args: &[String]) -> String {
+    let mut formatted = String::with_capacity(template.len());
+    let mut pos = 0;
+
+    while let Some(found) = template[pos..].find('{') {
+        let start = pos + found;
+        formatted.push_str(&template[pos..start]);
+        let arg = template[start..]
+            .find('}')
+            .and_then(|len| template[start + 1..start + len].parse::<usize>().ok().map(|index| (index, len)))
+            .and_then(|(index, len)| args.get(index).map(|arg| (arg, len)));
+
+        match arg {
+            Some((arg, len)) => {
+                formatted.push_str(arg);
+                pos = start + len + 1;
+            }
+            None => {
+                formatted.push('{');
+                pos = start + 1;
+            }
+        }
+    }
+
+    formatted.push_str(&template[pos..]);
+    formatted
+}
+
+// Looks a message up in the config, falling back to the stock text
+pub fn message_text(config: &Value, key: &str) -> String {
+    config
+        .pointer(&format!("/Messages/{}", key))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .or_else(|| message_schema().remove(key).map(|schema| schema.default_text))
+        .unwrap_or_default()
+}
+
+pub fn message_schema() -> BTreeMap<String, MessageSchema> {
+    let mut schema = BTreeMap::new();
+    let defaults = default_config();
+
+    if let Some(messages) = defaults.get("Messages").and_then(Value::as_object) {
+        for (key, value) in messages {
+            let text = value.as_str().unwrap_or_default().to_string();
+            let placeholders: BTreeSet<u32> = scan_placeholders(&text).0.into_iter().collect();
+            schema.insert(key.clone(), MessageSchema {
+                key: key.clone(),
+                placeholders: placeholders.into_iter().collect(),
+                default_text: text,
+            });
+        }
+    }
+
+    schema
+}
+
+pub fn validate_message_placeholders(config: &Value) -> Vec<PlaceholderIssue> {
+    let mut issues = Vec::new();
+    let messages = match config.get("Messages").and_then(Value::as_object) {
+        Some(messages) => messages,
+        None => return issues,
+    };
+    let schema = message_schema();
+
+    for (key, value) in messages {
+        let text = match value.as_str() {
+            Some(text) => text,
+            None => continue,
+        };
+
+        let expected = match schema.get(key) {
+            Some(expected) => expected,
+            None => {
+                issues.push(PlaceholderIssue {
+                    key: key.clone(),
+                    severity: IssueSeverity::Warning,
+                    problem: PlaceholderProblem::UnknownKey,
+                    placeholder: None,
+                    message: format!("\"{}\" is not a message the plugin reads and will be ignored", key),
+                });
+                continue;
+            }
+        };
+
+        let (indices, malformed) = scan_placeholders(text);
+        let used: BTreeSet<u32> = indices.into_iter().collect();
+        // The plugin passes exactly as many arguments as the stock message uses
+        let argument_count = expected.placeholders.iter().max().map_or(0, |max| max + 1);
+
+        for index in &expected.placeholders {
+            if !used.contains(index) {
+                issues.push(PlaceholderIssue {
+                    key: key.clone(),
+                    severity: IssueSeverity::Warning,
+                    problem: PlaceholderProblem::Missing,
+                    placeholder: Some(format!("{{{}}}", index)),
+                    message: format!("Placeholder {{{}}} is missing, that value won't be shown", index),
+                });
+            }
+        }
+
+        for index in &used {
+            if argument_count == 0 {
+                issues.push(PlaceholderIssue {
+                    key: key.clone(),
+                    severity: IssueSeverity::Error,
+                    problem: PlaceholderProblem::Extra,
+                    placeholder: Some(format!("{{{}}}", index)),
+                    message: format!("This message takes no placeholders, {{{}}} will be printed as-is", index),
+                });
+            } else if *index >= argument_count {
+                issues.push(PlaceholderIssue {
+                    key: key.clone(),
+                    severity: IssueSeverity::Error,
+                    problem: PlaceholderProblem::OutOfRange,
+                    placeholder: Some(format!("{{{}}}", index)),
+                    message: format!("Placeholder {{{}}} is out of range, only {{0}} to {{{}}} are filled in", index, argument_count - 1),
+                });
+            }
+        }
+
+        for group in malformed {
+            issues.push(PlaceholderIssue {
+                key: key.clone(),
+                severity: IssueSeverity::Error,
+                problem: PlaceholderProblem::Malformed,
+                message: format!("\"{}\" is not a valid placeholder, use {{0}}, {{1}} and so on", group),
+                placeholder: Some(group),
+            });
+        }
+    }
+
+    issues
+}
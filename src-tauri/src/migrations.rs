@@ -0,0 +1,390 @@
+// src-tauri/src/migrations.rs
+
+use std::path::{Path, PathBuf};
+use chrono::Utc;
+use rusqlite::{params, Connection, Error, Result, Transaction};
+
+pub struct Migration {
+    pub name: &'static str,
+    apply: fn(&Transaction) -> Result<()>,
+}
+
+// Step N brings a database at user_version N to N + 1. Steps are only ever appended.
+// Databases from before user_version was tracked sit at 0 with any subset of these
+// tables, so the early steps use IF NOT EXISTS.
+pub const MIGRATIONS: [Migration; 7] = [
+    Migration { name: "initial schema", apply: initial_schema },
+    Migration { name: "message packs", apply: message_packs },
+    Migration { name: "permissions source", apply: permissions_source },
+    Migration { name: "export path maps", apply: export_path_maps },
+    Migration { name: "command deny list", apply: command_deny_list },
+    Migration { name: "secret key", apply: secret_key },
+    Migration { name: "workspace backup schedule", apply: workspace_backup_schedule },
+];
+
+pub fn schema_version() -> i64 {
+    MIGRATIONS.len() as i64
+}
+
+fn failure(message: String) -> Error {
+    Error::SqliteFailure(rusqlite::ffi::Error::new(1), Some(message))
+}
+
+fn column_exists(tx: &Transaction, table: &str, column: &str) -> Result<bool> {
+    let mut stmt = tx.prepare(&format!("PRAGMA table_info({})", table))?;
+    let mut names = stmt.query_map([], |row| row.get::<_, String>(1))?;
+    names.try_fold(false, |found, name| Ok(found || name? == column))
+}
+
+fn add_column_if_missing(tx: &Transaction, table: &str, column: &str, definition: &str) -> Result<()> {
+    if !column_exists(tx, table, column)? {
+        tx.execute(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition), [])?;
+    }
+    Ok(())
+}
+
+fn initial_schema(tx: &Transaction) -> Result<()> {
+    tx.execute(
+        "CREATE TABLE IF NOT EXISTS settings (
+            id INTEGER PRIMARY KEY,
+            output_path TEXT NOT NULL,
+            auto_save_enabled BOOLEAN NOT NULL DEFAULT 0,
+            auto_save_interval INTEGER NOT NULL DEFAULT 5,
+            show_tooltips BOOLEAN NOT NULL DEFAULT 1
+        )",
+        [],
+    )?;
+    tx.execute(
+        "CREATE TABLE IF NOT EXISTS saved_configs (
+            id INTEGER PRIMARY KEY,
+            name TEXT NOT NULL UNIQUE,
+            config TEXT NOT NULL,
+            custom_export_paths TEXT
+        )",
+        [],
+    )?;
+    tx.execute(
+        "CREATE TABLE IF NOT EXISTS license_info (
+            id INTEGER PRIMARY KEY,
+            license_key TEXT NOT NULL,
+            expiration_date TEXT NOT NULL,
+            hwid TEXT NOT NULL
+        )",
+        [],
+    )?;
+    // Older databases may predate these columns
+    add_column_if_missing(tx, "settings", "show_tooltips", "BOOLEAN NOT NULL DEFAULT 1")?;
+    add_column_if_missing(tx, "saved_configs", "custom_export_paths", "TEXT")?;
+    Ok(())
+}
+
+fn message_packs(tx: &Transaction) -> Result<()> {
+    tx.execute(
+        "CREATE TABLE IF NOT EXISTS message_packs (
+            id INTEGER PRIMARY KEY,
+            name TEXT NOT NULL UNIQUE,
+            messages TEXT NOT NULL,
+            is_default BOOLEAN NOT NULL DEFAULT 0
+        )",
+        [],
+    )?;
+    tx.execute(
+        "CREATE TABLE IF NOT EXISTS config_message_packs (
+            config_id INTEGER PRIMARY KEY,
+            pack_id INTEGER NOT NULL
+        )",
+        [],
+    )?;
+    tx.execute(
+        "CREATE TABLE IF NOT EXISTS export_path_message_packs (
+            path TEXT PRIMARY KEY,
+            pack_id INTEGER NOT NULL
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+fn permissions_source(tx: &Transaction) -> Result<()> {
+    tx.execute(
+        "CREATE TABLE IF NOT EXISTS permissions_source (
+            id INTEGER PRIMARY KEY,
+            source TEXT NOT NULL
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+fn export_path_maps(tx: &Transaction) -> Result<()> {
+    tx.execute(
+        "CREATE TABLE IF NOT EXISTS export_path_maps (
+            path TEXT PRIMARY KEY,
+            map TEXT NOT NULL,
+            drop_incompatible BOOLEAN NOT NULL DEFAULT 0
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+fn command_deny_list(tx: &Transaction) -> Result<()> {
+    tx.execute(
+        "CREATE TABLE IF NOT EXISTS command_deny_list (
+            id INTEGER PRIMARY KEY,
+            patterns TEXT NOT NULL
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+fn secret_key(tx: &Transaction) -> Result<()> {
+    tx.execute(
+        "CREATE TABLE IF NOT EXISTS secret_key (
+            id INTEGER PRIMARY KEY,
+            source TEXT NOT NULL,
+            salt TEXT,
+            verifier TEXT NOT NULL
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+fn workspace_backup_schedule(tx: &Transaction) -> Result<()> {
+    tx.execute(
+        "CREATE TABLE workspace_backup_schedule (
+            id INTEGER PRIMARY KEY,
+            directory TEXT NOT NULL,
+            interval_hours INTEGER NOT NULL,
+            retention INTEGER NOT NULL,
+            last_backup TEXT
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+pub fn user_version(conn: &Connection) -> Result<i64> {
+    conn.query_row("PRAGMA user_version", [], |row| row.get(0))
+}
+
+// A fresh database has nothing worth backing up
+fn has_tables(conn: &Connection) -> Result<bool> {
+    let count: i64 = conn.query_row("SELECT COUNT(*) FROM sqlite_master WHERE type = 'table'", [], |row| row.get(0))?;
+    Ok(count > 0)
+}
+
+// Written next to the database as settings.db.v<version>-<timestamp>.bak
+pub fn backup_database(conn: &Connection, db_path: &Path, version: i64) -> Result<PathBuf> {
+    let file_name = db_path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_else(|| "settings.db".to_string());
+    let backup_path = db_path.with_file_name(format!("{}.v{}-{}.bak", file_name, version, Utc::now().format("%Y%m%d%H%M%S")));
+    conn.execute("VACUUM INTO ?1", params![backup_path.to_string_lossy()])?;
+    Ok(backup_path)
+}
+
+// Brings the database up to schema_version(), one transaction per step.
+// Returns the backup taken beforehand, if migrating was needed.
+pub fn migrate(conn: &mut Connection, db_path: &Path) -> Result<Option<PathBuf>> {
+    let current = user_version(conn)?;
+    let target = schema_version();
+    if current > target {
+        return Err(failure(format!(
+            "settings.db is at schema version {} but this version of the app only supports up to {}, please update the app",
+            current, target
+        )));
+    }
+    if current == target {
+        return Ok(None);
+    }
+
+    let backup = if has_tables(conn)? {
+        Some(backup_database(conn, db_path, current)?)
+    } else {
+        None
+    };
+
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(current as usize) {
+        let version = index as i64 + 1;
+        let tx = conn.transaction()?;
+        (migration.apply)(&tx).map_err(|e| failure(format!("Migration {} ({}) failed: {}", version, migration.name, e)))?;
+        tx.pragma_update(None, "user_version", version)?;
+        tx.commit()?;
+    }
+
+    Ok(backup)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    struct TempDb {
+        path: PathBuf,
+    }
+
+    impl TempDb {
+        fn new(name: &str) -> TempDb {
+            let db = TempDb { path: std::env::temp_dir().join(format!("arkshop-migrate-{}-{}.db", name, std::process::id())) };
+            db.clean();
+            db
+        }
+
+        fn open(&self) -> Connection {
+            Connection::open(&self.path).unwrap()
+        }
+
+        // The database and any backups taken of it
+        fn clean(&self) {
+            let prefix = self.path.file_name().unwrap().to_string_lossy().into_owned();
+            for entry in fs::read_dir(std::env::temp_dir()).unwrap().flatten() {
+                if entry.file_name().to_string_lossy().starts_with(&prefix) {
+                    let _ = fs::remove_file(entry.path());
+                }
+            }
+        }
+    }
+
+    impl Drop for TempDb {
+        fn drop(&mut self) {
+            self.clean();
+        }
+    }
+
+    // Runs the first `steps` migrations without touching user_version
+    fn apply_steps(conn: &mut Connection, steps: usize) {
+        let tx = conn.transaction().unwrap();
+        for migration in &MIGRATIONS[..steps] {
+            (migration.apply)(&tx).unwrap();
+        }
+        tx.commit().unwrap();
+    }
+
+    fn columns(conn: &Connection, table: &str) -> Vec<String> {
+        let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table)).unwrap();
+        let names = stmt.query_map([], |row| row.get(1)).unwrap();
+        names.map(|name| name.unwrap()).collect()
+    }
+
+    fn assert_current_schema(conn: &Connection) {
+        assert_eq!(user_version(conn).unwrap(), schema_version());
+        for table in [
+            "settings",
+            "saved_configs",
+            "license_info",
+            "message_packs",
+            "config_message_packs",
+            "export_path_message_packs",
+            "permissions_source",
+            "export_path_maps",
+            "command_deny_list",
+            "secret_key",
+            "workspace_backup_schedule",
+        ] {
+            assert!(!columns(conn, table).is_empty(), "{} is missing", table);
+        }
+        assert!(columns(conn, "settings").contains(&"show_tooltips".to_string()));
+        assert!(columns(conn, "saved_configs").contains(&"custom_export_paths".to_string()));
+        assert!(columns(conn, "export_path_maps").contains(&"drop_incompatible".to_string()));
+    }
+
+    fn assert_backed_up(backup: Option<PathBuf>, version: i64) {
+        let backup = backup.expect("no backup was taken");
+        assert!(backup.exists());
+        assert!(backup.to_string_lossy().contains(&format!(".v{}-", version)));
+        assert_eq!(user_version(&Connection::open(&backup).unwrap()).unwrap(), version);
+    }
+
+    #[test]
+    fn migrates_the_baseline_database() {
+        let db = TempDb::new("baseline");
+        let mut conn = db.open();
+        conn.execute_batch(
+            "CREATE TABLE settings (
+                id INTEGER PRIMARY KEY,
+                output_path TEXT NOT NULL,
+                auto_save_enabled BOOLEAN NOT NULL DEFAULT 0,
+                auto_save_interval INTEGER NOT NULL DEFAULT 5
+            );
+            CREATE TABLE saved_configs (
+                id INTEGER PRIMARY KEY,
+                name TEXT NOT NULL UNIQUE,
+                config TEXT NOT NULL
+            );
+            CREATE TABLE license_info (
+                id INTEGER PRIMARY KEY,
+                license_key TEXT NOT NULL,
+                expiration_date TEXT NOT NULL,
+                hwid TEXT NOT NULL
+            );
+            INSERT INTO settings (id, output_path) VALUES (1, 'C:\\ArkShop');
+            INSERT INTO saved_configs (name, config) VALUES ('main', '{}');",
+        ).unwrap();
+
+        let backup = migrate(&mut conn, &db.path).unwrap();
+
+        assert_backed_up(backup, 0);
+        assert_current_schema(&conn);
+        let (output_path, show_tooltips): (String, bool) = conn
+            .query_row("SELECT output_path, show_tooltips FROM settings WHERE id = 1", [], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap();
+        assert_eq!(output_path, "C:\\ArkShop");
+        assert!(show_tooltips);
+        let paths: Option<String> = conn.query_row("SELECT custom_export_paths FROM saved_configs", [], |row| row.get(0)).unwrap();
+        assert_eq!(paths, None);
+    }
+
+    #[test]
+    fn migrates_untracked_databases_that_already_have_every_table() {
+        let db = TempDb::new("untracked");
+        let mut conn = db.open();
+        // Everything that existed before user_version was tracked
+        apply_steps(&mut conn, 6);
+        assert_eq!(user_version(&conn).unwrap(), 0);
+
+        let backup = migrate(&mut conn, &db.path).unwrap();
+
+        assert_backed_up(backup, 0);
+        assert_current_schema(&conn);
+    }
+
+    #[test]
+    fn migrates_every_intermediate_version() {
+        for version in 1..schema_version() {
+            let db = TempDb::new(&format!("v{}", version));
+            let mut conn = db.open();
+            apply_steps(&mut conn, version as usize);
+            conn.pragma_update(None, "user_version", version).unwrap();
+
+            let backup = migrate(&mut conn, &db.path).unwrap();
+
+            assert_backed_up(backup, version);
+            assert_current_schema(&conn);
+        }
+    }
+
+    #[test]
+    fn fresh_databases_are_not_backed_up() {
+        let db = TempDb::new("fresh");
+        let mut conn = db.open();
+
+        assert_eq!(migrate(&mut conn, &db.path).unwrap(), None);
+        assert_current_schema(&conn);
+        assert_eq!(migrate(&mut conn, &db.path).unwrap(), None);
+    }
+
+    #[test]
+    fn refuses_databases_from_a_newer_app() {
+        let db = TempDb::new("newer");
+        let mut conn = db.open();
+        apply_steps(&mut conn, MIGRATIONS.len());
+        conn.pragma_update(None, "user_version", schema_version() + 1).unwrap();
+
+        let error = migrate(&mut conn, &db.path).unwrap_err().to_string();
+
+        assert!(error.contains("please update the app"), "{}", error);
+        assert_eq!(user_version(&conn).unwrap(), schema_version() + 1);
+    }
+}
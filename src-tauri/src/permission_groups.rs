@@ -0,0 +1,182 @@
+// src-tauri/src/permission_groups.rs
+
+use std::collections::BTreeMap;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use crate::config_refs::{pointer, ENTRY_SECTIONS};
+
+const TIMED_REWARD_GROUPS: &str = "/General/TimedPointsReward/Groups";
+
+#[derive(Serialize, Debug, Clone)]
+pub struct GroupRef {
+    pub pointer: String,
+    pub group: String,
+    // True for TimedPointsReward.Groups, where the group name is the object key
+    pub is_key: bool,
+}
+
+#[derive(Serialize, Debug)]
+pub struct GroupUsage {
+    pub name: String,
+    pub count: usize,
+    pub locations: Vec<String>,
+    pub likely_typo: bool,
+    pub similar_to: Vec<String>,
+}
+
+// ArkShop's Permissions field is a comma separated list of group names
+pub fn parse_permissions(permissions: &str) -> Vec<String> {
+    permissions
+        .split(',')
+        .map(str::trim)
+        .filter(|group| !group.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+pub fn group_refs(config: &Value) -> Vec<GroupRef> {
+    let mut refs = Vec::new();
+
+    for section in ENTRY_SECTIONS.iter() {
+        let entries = match config.get(*section).and_then(Value::as_object) {
+            Some(entries) => entries,
+            None => continue,
+        };
+        for (key, entry) in entries {
+            if let Some(permissions) = entry.get("Permissions").and_then(Value::as_str) {
+                for group in parse_permissions(permissions) {
+                    refs.push(GroupRef {
+                        pointer: pointer(&[section, key, "Permissions"]),
+                        group,
+                        is_key: false,
+                    });
+                }
+            }
+        }
+    }
+
+    if let Some(groups) = config.pointer(TIMED_REWARD_GROUPS).and_then(Value::as_object) {
+        for group in groups.keys() {
+            refs.push(GroupRef {
+                pointer: format!("{}{}", TIMED_REWARD_GROUPS, pointer(&[group])),
+                group: group.clone(),
+                is_key: true,
+            });
+        }
+    }
+
+    refs
+}
+
+// Optimal string alignment distance, so a swapped pair of letters counts as one edit
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut d = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1).min(d[i][j - 1] + 1).min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+
+    d[a.len()][b.len()]
+}
+
+pub fn is_similar_group(a: &str, b: &str) -> bool {
+    if a == b {
+        return false;
+    }
+    let max_edits = if a.len().max(b.len()) <= 4 { 1 } else { 2 };
+    edit_distance(&a.to_lowercase(), &b.to_lowercase()) <= max_edits
+}
+
+pub fn group_inventory(config: &Value) -> Vec<GroupUsage> {
+    let mut usages: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for reference in group_refs(config) {
+        usages.entry(reference.group).or_default().push(reference.pointer);
+    }
+
+    let names: Vec<String> = usages.keys().cloned().collect();
+    usages
+        .into_iter()
+        .map(|(name, locations)| {
+            let similar_to = names
+                .iter()
+                .filter(|other| is_similar_group(&name, other))
+                .cloned()
+                .collect();
+            GroupUsage {
+                likely_typo: locations.len() == 1,
+                count: locations.len(),
+                name,
+                locations,
+                similar_to,
+            }
+        })
+        .collect()
+}
+
+// Rewrites every reference to `from` in one pass and returns the pointers it touched
+pub fn rename_group(config: &mut Value, from: &str, to: &str) -> Result<Vec<String>, String> {
+    let to = to.trim();
+    if to.is_empty() {
+        return Err("New group name must not be empty".to_string());
+    }
+    if to.contains(',') {
+        return Err("Group names can't contain commas".to_string());
+    }
+    if from == to {
+        return Ok(Vec::new());
+    }
+
+    let refs: Vec<GroupRef> = group_refs(config).into_iter().filter(|r| r.group == from).collect();
+    if refs.is_empty() {
+        return Err(format!("Group \"{}\" is not used in this config", from));
+    }
+
+    if let Some(groups) = config.pointer(TIMED_REWARD_GROUPS).and_then(Value::as_object) {
+        if groups.contains_key(from) && groups.contains_key(to) {
+            return Err(format!("TimedPointsReward already has a reward for group \"{}\"", to));
+        }
+    }
+
+    let mut rewritten = Vec::new();
+    for reference in refs {
+        if reference.is_key {
+            if let Some(groups) = config.pointer_mut(TIMED_REWARD_GROUPS).and_then(Value::as_object_mut) {
+                let renamed: Map<String, Value> = std::mem::take(groups)
+                    .into_iter()
+                    .map(|(group, reward)| if group == from { (to.to_string(), reward) } else { (group, reward) })
+                    .collect();
+                *groups = renamed;
+            }
+        } else if let Some(Value::String(permissions)) = config.pointer_mut(&reference.pointer) {
+            let mut groups: Vec<String> = Vec::new();
+            for group in parse_permissions(permissions) {
+                let group = if group == from { to.to_string() } else { group };
+                if !groups.contains(&group) {
+                    groups.push(group);
+                }
+            }
+            *permissions = groups.join(",");
+        }
+
+        if !rewritten.contains(&reference.pointer) {
+            rewritten.push(reference.pointer);
+        }
+    }
+
+    Ok(rewritten)
+}
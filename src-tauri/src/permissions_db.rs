@@ -0,0 +1,182 @@
+// src-tauri/src/permissions_db.rs
+
+use std::collections::BTreeMap;
+use mysql::prelude::Queryable;
+use rusqlite::{Connection, OpenFlags};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::permission_groups::{group_refs, is_similar_group};
+
+// Table names the ArkApi Permissions plugin has used for its group list
+const GROUP_TABLES: [&str; 2] = ["PermissionGroups", "Groups"];
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum PermissionsSource {
+    Sqlite {
+        path: String,
+    },
+    Mysql {
+        host: String,
+        user: String,
+        password: String,
+        database: String,
+        port: u16,
+    },
+}
+
+impl PermissionsSource {
+    // Reuses the ArkShop Mysql section, optionally pointing at a different schema
+    pub fn from_config_mysql(config: &Value, database: Option<String>) -> Result<PermissionsSource, String> {
+        let mysql = config.get("Mysql").ok_or("Config has no Mysql section")?;
+        let text = |field: &str| mysql.get(field).and_then(Value::as_str).unwrap_or_default().to_string();
+
+        let host = text("MysqlHost");
+        if host.is_empty() {
+            return Err("Mysql.MysqlHost is not set".to_string());
+        }
+        let port = mysql.get("MysqlPort").and_then(Value::as_u64).unwrap_or(3306);
+
+        Ok(PermissionsSource::Mysql {
+            host,
+            user: text("MysqlUser"),
+            password: text("MysqlPass"),
+            database: database.unwrap_or_else(|| text("MysqlDB")),
+            port: u16::try_from(port).map_err(|_| "Mysql.MysqlPort is out of range".to_string())?,
+        })
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct UnknownGroup {
+    pub group: String,
+    pub locations: Vec<String>,
+    pub suggestions: Vec<String>,
+}
+
+pub fn read_groups_sqlite(conn: &Connection) -> Result<Vec<String>, String> {
+    let mut last_error = String::new();
+    for table in GROUP_TABLES.iter() {
+        let query = format!("SELECT GroupName FROM {}", table);
+        let mut stmt = match conn.prepare(&query) {
+            Ok(stmt) => stmt,
+            Err(e) => {
+                last_error = e.to_string();
+                continue;
+            }
+        };
+        let rows = stmt.query_map([], |row| row.get::<_, String>(0)).map_err(|e| e.to_string())?;
+
+        let mut groups = Vec::new();
+        for group in rows {
+            groups.push(group.map_err(|e| e.to_string())?);
+        }
+        return Ok(groups);
+    }
+    Err(format!("No Permissions group table found: {}", last_error))
+}
+
+fn read_groups_mysql(host: &str, user: &str, password: &str, database: &str, port: u16) -> Result<Vec<String>, String> {
+    let opts = mysql::OptsBuilder::new()
+        .ip_or_hostname(Some(host))
+        .user(Some(user))
+        .pass(Some(password))
+        .db_name(Some(database))
+        .tcp_port(port);
+    let mut conn = mysql::Conn::new(opts).map_err(|e| format!("Failed to connect to MySQL: {}", e))?;
+
+    let mut last_error = String::new();
+    for table in GROUP_TABLES.iter() {
+        match conn.query::<String, _>(format!("SELECT GroupName FROM {}", table)) {
+            Ok(groups) => return Ok(groups),
+            Err(e) => last_error = e.to_string(),
+        }
+    }
+    Err(format!("No Permissions group table found: {}", last_error))
+}
+
+pub fn read_permission_groups(source: &PermissionsSource) -> Result<Vec<String>, String> {
+    let mut groups = match source {
+        PermissionsSource::Sqlite { path } => {
+            let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+                .map_err(|e| format!("Failed to open Permissions database: {}", e))?;
+            read_groups_sqlite(&conn)?
+        }
+        PermissionsSource::Mysql { host, user, password, database, port } => {
+            read_groups_mysql(host, user, password, database, *port)?
+        }
+    };
+    groups.sort();
+    groups.dedup();
+    Ok(groups)
+}
+
+pub fn find_unknown_groups(config: &Value, server_groups: &[String]) -> Vec<UnknownGroup> {
+    let mut unknown: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for reference in group_refs(config) {
+        // ArkApi matches group names case-insensitively
+        if !server_groups.iter().any(|known| known.eq_ignore_ascii_case(&reference.group)) {
+            unknown.entry(reference.group).or_default().push(reference.pointer);
+        }
+    }
+
+    unknown
+        .into_iter()
+        .map(|(group, locations)| {
+            let suggestions = server_groups
+                .iter()
+                .filter(|known| is_similar_group(&group, known))
+                .cloned()
+                .collect();
+            UnknownGroup { group, locations, suggestions }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn fixture_database(name: &str, table: &str, groups: &[&str]) -> String {
+        let path = std::env::temp_dir().join(format!("arkshop-{}-{}.db", name, std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let conn = Connection::open(&path).unwrap();
+        conn.execute(&format!("CREATE TABLE {} (Id INTEGER PRIMARY KEY, GroupName TEXT NOT NULL, Permissions TEXT)", table), []).unwrap();
+        for group in groups {
+            conn.execute(&format!("INSERT INTO {} (GroupName, Permissions) VALUES (?1, '')", table), [group]).unwrap();
+        }
+        path.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn reads_groups_and_reports_unknown_references() {
+        let path = fixture_database("permissions", "PermissionGroups", &["Admins", "Vip", "Default", "Vip"]);
+        let groups = read_permission_groups(&PermissionsSource::Sqlite { path: path.clone() }).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(groups, vec!["Admins", "Default", "Vip"]);
+
+        let config = json!({
+            "ShopItems": {
+                "dino": { "Type": "dino", "Permissions": "VIP" },
+                "ammo": { "Type": "item", "Permissions": "default,Admin" }
+            },
+            "Kits": {
+                "starter": { "Permissions": "Vipp" }
+            }
+        });
+        let unknown = find_unknown_groups(&config, &groups);
+        let names: Vec<&str> = unknown.iter().map(|group| group.group.as_str()).collect();
+        assert_eq!(names, vec!["Admin", "Vipp"]);
+        assert_eq!(unknown[0].suggestions, vec!["Admins"]);
+        assert_eq!(unknown[1].suggestions, vec!["Vip"]);
+    }
+
+    #[test]
+    fn reads_the_legacy_groups_table() {
+        let path = fixture_database("permissions-legacy", "Groups", &["Admins"]);
+        let groups = read_permission_groups(&PermissionsSource::Sqlite { path: path.clone() }).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(groups, vec!["Admins"]);
+    }
+}
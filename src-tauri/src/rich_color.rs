@@ -0,0 +1,289 @@
+// src-tauri/src/rich_color.rs
+
+use serde::Serialize;
+use serde_json::Value;
+
+const OPEN_TAG: &str = "<RichColor";
+const CLOSE_TAG: &str = "</>";
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq)]
+pub struct Rgba {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct TextRun {
+    pub text: String,
+    // None means the chat's default color
+    pub color: Option<Rgba>,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct MarkupIssue {
+    // Byte offset into the message where the problem starts
+    pub offset: usize,
+    pub message: String,
+}
+
+#[derive(Serialize, Debug, Default)]
+pub struct ParsedMessage {
+    pub runs: Vec<TextRun>,
+    pub issues: Vec<MarkupIssue>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct MessageMarkupIssue {
+    pub key: String,
+    pub offset: usize,
+    pub message: String,
+}
+
+fn parse_color_attribute(attributes: &str) -> Result<Rgba, String> {
+    let attributes = attributes.trim();
+    let value = attributes
+        .strip_prefix("Color")
+        .map(str::trim_start)
+        .and_then(|rest| rest.strip_prefix('='))
+        .map(str::trim)
+        .ok_or_else(|| "Expected Color=\"r, g, b, a\" attribute".to_string())?;
+
+    let inner = value
+        .strip_prefix('"')
+        .and_then(|rest| rest.strip_suffix('"'))
+        .ok_or_else(|| "Color value must be wrapped in double quotes".to_string())?;
+
+    let components: Vec<&str> = inner.split(',').map(str::trim).collect();
+    if components.len() != 4 {
+        return Err(format!("Color must have 4 components (r, g, b, a), found {}", components.len()));
+    }
+
+    let mut parsed = [0f32; 4];
+    for (index, component) in components.iter().enumerate() {
+        let number: f32 = component
+            .parse()
+            .map_err(|_| format!("Color component \"{}\" is not a number", component))?;
+        if !(0.0..=1.0).contains(&number) {
+            return Err(format!("Color component {} is outside the range 0-1", component));
+        }
+        parsed[index] = number;
+    }
+
+    Ok(Rgba { r: parsed[0], g: parsed[1], b: parsed[2], a: parsed[3] })
+}
+
+fn flush(runs: &mut Vec<TextRun>, text: &mut String, color: Option<Rgba>) {
+    if text.is_empty() {
+        return;
+    }
+    match runs.last_mut() {
+        Some(last) if last.color == color => last.text.push_str(text),
+        _ => runs.push(TextRun { text: text.clone(), color }),
+    }
+    text.clear();
+}
+
+pub fn parse_rich_text(input: &str) -> ParsedMessage {
+    let mut parsed = ParsedMessage::default();
+    let mut text = String::new();
+    let mut color: Option<Rgba> = None;
+    let mut open_at: Option<usize> = None;
+    let mut pos = 0;
+
+    while let Some(found) = input[pos..].find('<') {
+        let start = pos + found;
+        text.push_str(&input[pos..start]);
+        let rest = &input[start..];
+
+        if rest.starts_with(CLOSE_TAG) {
+            if open_at.is_none() {
+                parsed.issues.push(MarkupIssue {
+                    offset: start,
+                    message: "Closing </> without a matching <RichColor>".to_string(),
+                });
+            }
+            flush(&mut parsed.runs, &mut text, color);
+            color = None;
+            open_at = None;
+            pos = start + CLOSE_TAG.len();
+        } else if rest.starts_with(OPEN_TAG) {
+            let end = match rest.find('>') {
+                Some(end) => end,
+                None => {
+                    parsed.issues.push(MarkupIssue {
+                        offset: start,
+                        message: "Unterminated <RichColor tag".to_string(),
+                    });
+                    text.push_str(rest);
+                    pos = input.len();
+                    break;
+                }
+            };
+
+            if open_at.is_some() {
+                parsed.issues.push(MarkupIssue {
+                    offset: start,
+                    message: "Nested <RichColor> tags are not supported, close the previous one with </> first".to_string(),
+                });
+            }
+            flush(&mut parsed.runs, &mut text, color);
+
+            color = match parse_color_attribute(&rest[OPEN_TAG.len()..end]) {
+                Ok(rgba) => Some(rgba),
+                Err(message) => {
+                    parsed.issues.push(MarkupIssue { offset: start, message });
+                    None
+                }
+            };
+            open_at = Some(start);
+            pos = start + end + 1;
+        } else {
+            if rest.starts_with("</") {
+                parsed.issues.push(MarkupIssue {
+                    offset: start,
+                    message: "Malformed closing tag, expected </>".to_string(),
+                });
+            }
+            text.push('<');
+            pos = start + 1;
+        }
+    }
+
+    text.push_str(&input[pos..]);
+    if let Some(offset) = open_at {
+        parsed.issues.push(MarkupIssue {
+            offset,
+            message: "<RichColor> is never closed with </>".to_string(),
+        });
+    }
+    flush(&mut parsed.runs, &mut text, color);
+
+    parsed
+}
+
+pub fn strip_rich_text(input: &str) -> String {
+    parse_rich_text(input).runs.into_iter().map(|run| run.text).collect()
+}
+
+pub fn validate_messages_markup(config: &Value) -> Vec<MessageMarkupIssue> {
+    let mut issues = Vec::new();
+    let messages = match config.get("Messages").and_then(Value::as_object) {
+        Some(messages) => messages,
+        None => return issues,
+    };
+
+    for (key, value) in messages {
+        match value.as_str() {
+            Some(message) => {
+                for issue in parse_rich_text(message).issues {
+                    issues.push(MessageMarkupIssue {
+                        key: key.clone(),
+                        offset: issue.offset,
+                        message: issue.message,
+                    });
+                }
+            }
+            None => issues.push(MessageMarkupIssue {
+                key: key.clone(),
+                offset: 0,
+                message: "Message must be a string".to_string(),
+            }),
+        }
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const RED: Rgba = Rgba { r: 1.0, g: 0.0, b: 0.0, a: 1.0 };
+
+    fn runs(input: &str) -> Vec<(String, Option<Rgba>)> {
+        parse_rich_text(input).runs.into_iter().map(|run| (run.text, run.color)).collect()
+    }
+
+    fn issues(input: &str) -> Vec<(usize, String)> {
+        parse_rich_text(input).issues.into_iter().map(|issue| (issue.offset, issue.message)).collect()
+    }
+
+    #[test]
+    fn splits_text_into_colored_runs() {
+        let input = "Bought <RichColor Color=\"1, 0, 0, 1\">Metal</> for 10";
+        assert!(issues(input).is_empty());
+        assert_eq!(runs(input), vec![
+            ("Bought ".to_string(), None),
+            ("Metal".to_string(), Some(RED)),
+            (" for 10".to_string(), None),
+        ]);
+        assert_eq!(strip_rich_text(input), "Bought Metal for 10");
+    }
+
+    #[test]
+    fn merges_adjacent_runs_with_the_same_color() {
+        let input = "<RichColor Color=\"1,0,0,1\">Me</><RichColor Color=\" 1.0, 0, 0.0, 1 \">tal</>";
+        assert_eq!(runs(input), vec![("Metal".to_string(), Some(RED))]);
+        // A tag whose color can't be read falls back to the default color
+        assert_eq!(runs("a<RichColor Color=\"red\">b</>c"), vec![("abc".to_string(), None)]);
+    }
+
+    #[test]
+    fn reports_unbalanced_tags() {
+        assert_eq!(issues("<RichColor Color=\"1,0,0,1\">Metal"), vec![(0, "<RichColor> is never closed with </>".to_string())]);
+        assert_eq!(runs("<RichColor Color=\"1,0,0,1\">Metal"), vec![("Metal".to_string(), Some(RED))]);
+        assert_eq!(issues("Metal</>"), vec![(5, "Closing </> without a matching <RichColor>".to_string())]);
+        assert_eq!(issues("a <RichColor Color=\"1,0,0,1\""), vec![(2, "Unterminated <RichColor tag".to_string())]);
+
+        let nested = "<RichColor Color=\"1,0,0,1\">a<RichColor Color=\"0,1,0,1\">b</>";
+        assert_eq!(issues(nested), vec![(
+            28,
+            "Nested <RichColor> tags are not supported, close the previous one with </> first".to_string(),
+        )]);
+    }
+
+    #[test]
+    fn stray_closing_tags_stay_in_the_text() {
+        assert_eq!(issues("a </x b"), vec![(2, "Malformed closing tag, expected </>".to_string())]);
+        assert_eq!(strip_rich_text("a </x b"), "a </x b");
+        // A lone < isn't markup
+        assert!(issues("1 < 2").is_empty());
+    }
+
+    #[test]
+    fn rejects_bad_color_components() {
+        let cases = [
+            ("1.5, 0, 0, 1", "Color component 1.5 is outside the range 0-1"),
+            ("1, -0.1, 0, 1", "Color component -0.1 is outside the range 0-1"),
+            ("1, 0, 0", "Color must have 4 components (r, g, b, a), found 3"),
+            ("1, 0, 0, 1, 1", "Color must have 4 components (r, g, b, a), found 5"),
+            ("1, 0, x, 1", "Color component \"x\" is not a number"),
+        ];
+        for (components, message) in cases {
+            let input = format!("<RichColor Color=\"{}\">a</>", components);
+            assert_eq!(issues(&input), vec![(0, message.to_string())], "{}", components);
+        }
+        assert_eq!(issues("<RichColor Color=1,0,0,1>a</>")[0].1, "Color value must be wrapped in double quotes");
+        assert_eq!(issues("<RichColor Colour=\"1,0,0,1\">a</>")[0].1, "Expected Color=\"r, g, b, a\" attribute");
+    }
+
+    #[test]
+    fn offsets_are_bytes_into_multi_byte_text() {
+        // é is two bytes and each of 日本 three
+        assert_eq!(issues("héllo </x"), vec![(7, "Malformed closing tag, expected </>".to_string())]);
+        assert_eq!(issues("日本<RichColor Color=\"1,0,0,1\">ü")[0].0, 6);
+        assert_eq!(runs("日本<RichColor Color=\"1,0,0,1\">ü</>"), vec![
+            ("日本".to_string(), None),
+            ("ü".to_string(), Some(RED)),
+        ]);
+    }
+
+    #[test]
+    fn validates_every_message() {
+        let config = json!({ "Messages": { "Ok": "<RichColor Color=\"1,0,0,1\">hi</>", "Broken": "hi</>", "Number": 5 } });
+        let issues: Vec<(String, usize)> = validate_messages_markup(&config).into_iter().map(|issue| (issue.key, issue.offset)).collect();
+        assert_eq!(issues, vec![("Broken".to_string(), 2), ("Number".to_string(), 0)]);
+    }
+}
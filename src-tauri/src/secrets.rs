@@ -0,0 +1,323 @@
+// src-tauri/src/secrets.rs
+
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use argon2::Argon2;
+use base64::{Engine as _, engine::general_purpose};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// Config fields that are encrypted in settings.db and only decrypted for export
+pub const CONFIG_SECRETS: [&str; 2] = ["/Mysql/MysqlPass", "/General/Discord/URL"];
+// The password of a stored Permissions database source
+pub const SOURCE_SECRETS: [&str; 1] = ["/password"];
+
+// Shown in the editor in place of a sealed value while the key isn't available
+pub const LOCKED_PLACEHOLDER: &str = "<locked>";
+
+const SEALED_PREFIX: &str = "enc:v1:";
+const NONCE_LEN: usize = 12;
+const SALT_LEN: usize = 16;
+const VERIFIER_TEXT: &str = "arkshop-secrets";
+
+const KEYRING_SERVICE: &str = "ASA ArkShop Config Editor";
+const KEYRING_USER: &str = "config-secrets";
+
+pub type SecretKey = [u8; 32];
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum KeySource {
+    Keyring,
+    Passphrase,
+}
+
+impl KeySource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KeySource::Keyring => "keyring",
+            KeySource::Passphrase => "passphrase",
+        }
+    }
+
+    pub fn parse(source: &str) -> Result<KeySource, String> {
+        match source {
+            "keyring" => Ok(KeySource::Keyring),
+            "passphrase" => Ok(KeySource::Passphrase),
+            _ => Err(format!("Unknown secrets key source \"{}\"", source)),
+        }
+    }
+}
+
+pub fn is_sealed(value: &str) -> bool {
+    value.starts_with(SEALED_PREFIX)
+}
+
+pub fn random_key() -> SecretKey {
+    Aes256Gcm::generate_key(&mut OsRng).into()
+}
+
+pub fn new_salt() -> String {
+    let mut salt = [0u8; SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+    general_purpose::STANDARD.encode(salt)
+}
+
+pub fn derive_key(passphrase: &str, salt: &str) -> Result<SecretKey, String> {
+    let salt = general_purpose::STANDARD.decode(salt).map_err(|e| format!("Invalid passphrase salt: {}", e))?;
+    let mut key = [0u8; 32];
+    Argon2::default()
+        .hash_password_into(passphrase.as_bytes(), &salt, &mut key)
+        .map_err(|e| format!("Failed to derive key from passphrase: {}", e))?;
+    Ok(key)
+}
+
+// Sealed values are "enc:v1:" followed by base64 of the nonce and AES-256-GCM ciphertext
+pub fn seal(key: &SecretKey, plaintext: &str) -> Result<String, String> {
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_bytes())
+        .map_err(|_| "Failed to encrypt secret".to_string())?;
+
+    let mut sealed = nonce.to_vec();
+    sealed.extend_from_slice(&ciphertext);
+    Ok(format!("{}{}", SEALED_PREFIX, general_purpose::STANDARD.encode(sealed)))
+}
+
+pub fn open(key: &SecretKey, sealed: &str) -> Result<String, String> {
+    let encoded = sealed.strip_prefix(SEALED_PREFIX).ok_or("Value isn't encrypted")?;
+    let bytes = general_purpose::STANDARD.decode(encoded).map_err(|e| format!("Encrypted value is corrupt: {}", e))?;
+    if bytes.len() <= NONCE_LEN {
+        return Err("Encrypted value is corrupt".to_string());
+    }
+
+    let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+    let cipher = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key));
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| "Failed to decrypt secret, it was encrypted with a different key".to_string())?;
+    String::from_utf8(plaintext).map_err(|e| e.to_string())
+}
+
+pub fn verifier(key: &SecretKey) -> Result<String, String> {
+    seal(key, VERIFIER_TEXT)
+}
+
+pub fn verify(key: &SecretKey, verifier: &str) -> bool {
+    open(key, verifier).map_or(false, |text| text == VERIFIER_TEXT)
+}
+
+fn secret_text<'v>(value: &'v Value, pointer: &str) -> Option<&'v str> {
+    value.pointer(pointer).and_then(Value::as_str).filter(|text| !text.is_empty())
+}
+
+pub fn has_plain_secrets(value: &Value, pointers: &[&str]) -> bool {
+    pointers.iter().any(|pointer| secret_text(value, pointer).map_or(false, |text| !is_sealed(text)))
+}
+
+pub fn has_sealed_secrets(value: &Value, pointers: &[&str]) -> bool {
+    pointers.iter().any(|pointer| secret_text(value, pointer).map_or(false, is_sealed))
+}
+
+// Encrypts the plain text secrets; already sealed and empty fields are left alone
+pub fn seal_secrets(value: &mut Value, pointers: &[&str], key: &SecretKey) -> Result<(), String> {
+    for pointer in pointers {
+        if let Some(field) = value.pointer_mut(pointer) {
+            if let Some(text) = field.as_str().filter(|text| !text.is_empty() && !is_sealed(text)) {
+                *field = Value::String(seal(key, text)?);
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn open_secrets(value: &mut Value, pointers: &[&str], key: &SecretKey) -> Result<(), String> {
+    for pointer in pointers {
+        if let Some(field) = value.pointer_mut(pointer) {
+            if let Some(text) = field.as_str().filter(|text| is_sealed(text)) {
+                let text = open(key, text).map_err(|e| format!("{}: {}", pointer.trim_start_matches('/').replace('/', "."), e))?;
+                *field = Value::String(text);
+            }
+        }
+    }
+    Ok(())
+}
+
+// Blanks sealed fields whose key isn't available, so they can be re-entered
+pub fn clear_sealed_secrets(value: &mut Value, pointers: &[&str]) {
+    for pointer in pointers {
+        if let Some(field) = value.pointer_mut(pointer) {
+            if field.as_str().map_or(false, is_sealed) {
+                *field = Value::String(String::new());
+            }
+        }
+    }
+}
+
+pub fn has_locked_placeholders(value: &Value, pointers: &[&str]) -> bool {
+    pointers.iter().any(|pointer| value.pointer(pointer).and_then(Value::as_str) == Some(LOCKED_PLACEHOLDER))
+}
+
+pub fn mask_sealed_secrets(value: &mut Value, pointers: &[&str]) {
+    for pointer in pointers {
+        if let Some(field) = value.pointer_mut(pointer) {
+            if field.as_str().map_or(false, is_sealed) {
+                *field = Value::String(LOCKED_PLACEHOLDER.to_string());
+            }
+        }
+    }
+}
+
+// Puts the stored sealed value back wherever the editor still has the placeholder.
+// False when a placeholder has no sealed value to stand for.
+pub fn restore_locked_secrets(value: &mut Value, stored: Option<&Value>, pointers: &[&str]) -> bool {
+    let mut restored = true;
+    for pointer in pointers {
+        if let Some(field) = value.pointer_mut(pointer) {
+            if field.as_str() != Some(LOCKED_PLACEHOLDER) {
+                continue;
+            }
+            match stored.and_then(|stored| stored.pointer(pointer)).filter(|original| original.as_str().map_or(false, is_sealed)) {
+                Some(original) => *field = original.clone(),
+                None => restored = false,
+            }
+        }
+    }
+    restored
+}
+
+fn keyring_entry() -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER).map_err(|e| format!("OS keyring is unavailable: {}", e))
+}
+
+pub fn load_keyring_key() -> Result<Option<SecretKey>, String> {
+    let encoded = match keyring_entry()?.get_password() {
+        Ok(encoded) => encoded,
+        Err(keyring::Error::NoEntry) => return Ok(None),
+        Err(e) => return Err(format!("Failed to read the secrets key from the OS keyring: {}", e)),
+    };
+    let bytes = general_purpose::STANDARD.decode(encoded).map_err(|e| format!("Keyring secrets key is corrupt: {}", e))?;
+    SecretKey::try_from(bytes.as_slice()).map(Some).map_err(|_| "Keyring secrets key is corrupt".to_string())
+}
+
+pub fn store_keyring_key(key: &SecretKey) -> Result<(), String> {
+    keyring_entry()?
+        .set_password(&general_purpose::STANDARD.encode(key))
+        .map_err(|e| format!("Failed to store the secrets key in the OS keyring: {}", e))
+}
+
+pub fn delete_keyring_key() -> Result<(), String> {
+    match keyring_entry()?.delete_password() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(format!("Failed to remove the secrets key from the OS keyring: {}", e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn config() -> Value {
+        json!({
+            "Mysql": { "MysqlHost": "db", "MysqlPass": "hunter2" },
+            "General": { "Discord": { "URL": "" } }
+        })
+    }
+
+    #[test]
+    fn seal_round_trips_and_uses_a_fresh_nonce() {
+        let key = random_key();
+        let first = seal(&key, "hunter2").unwrap();
+        let second = seal(&key, "hunter2").unwrap();
+        assert!(is_sealed(&first));
+        assert_ne!(first, second);
+        assert_eq!(open(&key, &first).unwrap(), "hunter2");
+        assert_eq!(open(&key, &second).unwrap(), "hunter2");
+    }
+
+    #[test]
+    fn open_rejects_a_wrong_key() {
+        let sealed = seal(&random_key(), "hunter2").unwrap();
+        assert_eq!(open(&random_key(), &sealed).unwrap_err(), "Failed to decrypt secret, it was encrypted with a different key");
+    }
+
+    #[test]
+    fn open_rejects_corrupt_values() {
+        let key = random_key();
+        let sealed = seal(&key, "hunter2").unwrap();
+
+        assert!(open(&key, "hunter2").unwrap_err().contains("isn't encrypted"));
+        assert!(open(&key, "enc:v1:not base64!").unwrap_err().starts_with("Encrypted value is corrupt"));
+        assert_eq!(open(&key, "enc:v1:AAAA").unwrap_err(), "Encrypted value is corrupt");
+
+        let mut bytes = general_purpose::STANDARD.decode(sealed.strip_prefix(SEALED_PREFIX).unwrap()).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 1;
+        let tampered = format!("{}{}", SEALED_PREFIX, general_purpose::STANDARD.encode(bytes));
+        assert!(open(&key, &tampered).is_err());
+    }
+
+    #[test]
+    fn verify_only_accepts_the_matching_key() {
+        let key = random_key();
+        let check = verifier(&key).unwrap();
+        assert!(verify(&key, &check));
+        assert!(!verify(&random_key(), &check));
+        assert!(!verify(&key, "enc:v1:garbage"));
+    }
+
+    #[test]
+    fn passphrase_keys_depend_on_the_salt() {
+        let salt = new_salt();
+        assert_eq!(derive_key("correct horse", &salt).unwrap(), derive_key("correct horse", &salt).unwrap());
+        assert_ne!(derive_key("correct horse", &salt).unwrap(), derive_key("correct horse", &new_salt()).unwrap());
+        assert_ne!(derive_key("correct horse", &salt).unwrap(), derive_key("wrong horse", &salt).unwrap());
+    }
+
+    #[test]
+    fn seal_secrets_only_touches_plain_values() {
+        let key = random_key();
+        let mut value = config();
+        assert!(has_plain_secrets(&value, &CONFIG_SECRETS));
+
+        seal_secrets(&mut value, &CONFIG_SECRETS, &key).unwrap();
+        let sealed = value["Mysql"]["MysqlPass"].as_str().unwrap().to_string();
+        assert!(is_sealed(&sealed));
+        assert_eq!(value["General"]["Discord"]["URL"], "");
+        assert_eq!(value["Mysql"]["MysqlHost"], "db");
+        assert!(!has_plain_secrets(&value, &CONFIG_SECRETS));
+        assert!(has_sealed_secrets(&value, &CONFIG_SECRETS));
+
+        // Sealing again leaves the existing ciphertext alone
+        seal_secrets(&mut value, &CONFIG_SECRETS, &key).unwrap();
+        assert_eq!(value["Mysql"]["MysqlPass"], sealed.as_str());
+
+        let mut wrong = value.clone();
+        assert!(open_secrets(&mut wrong, &CONFIG_SECRETS, &random_key()).unwrap_err().starts_with("Mysql.MysqlPass: "));
+
+        open_secrets(&mut value, &CONFIG_SECRETS, &key).unwrap();
+        assert_eq!(value, config());
+    }
+
+    #[test]
+    fn locked_placeholders_restore_the_stored_value() {
+        let key = random_key();
+        let mut stored = config();
+        seal_secrets(&mut stored, &CONFIG_SECRETS, &key).unwrap();
+
+        let mut edited = stored.clone();
+        mask_sealed_secrets(&mut edited, &CONFIG_SECRETS);
+        assert_eq!(edited["Mysql"]["MysqlPass"], LOCKED_PLACEHOLDER);
+        assert!(has_locked_placeholders(&edited, &CONFIG_SECRETS));
+
+        assert!(restore_locked_secrets(&mut edited, Some(&stored), &CONFIG_SECRETS));
+        assert_eq!(edited, stored);
+
+        let mut orphan = edited.clone();
+        mask_sealed_secrets(&mut orphan, &CONFIG_SECRETS);
+        assert!(!restore_locked_secrets(&mut orphan, None, &CONFIG_SECRETS));
+    }
+}
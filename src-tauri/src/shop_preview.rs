@@ -0,0 +1,122 @@
+// src-tauri/src/shop_preview.rs
+
+use serde::Serialize;
+use serde_json::Value;
+use crate::message_schema::{format_placeholders, message_text};
+use crate::rich_color::strip_rich_text;
+
+// The plugin's fallback when General.ItemsPerPage is missing
+const DEFAULT_ITEMS_PER_PAGE: u64 = 20;
+
+#[derive(Serialize, Debug)]
+pub struct ShopLine {
+    // 1-based position in the listing, as printed by /shop
+    pub index: usize,
+    pub key: String,
+    pub entry_type: String,
+    pub text: String,
+    pub price: Option<f64>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ShopPage {
+    pub number: usize,
+    pub lines: Vec<ShopLine>,
+    pub text: String,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ShopPreview {
+    pub items_per_page: usize,
+    pub total_entries: usize,
+    pub total_pages: usize,
+    // How many entries land on the last page, and how many slots it leaves unused
+    pub last_page_entries: usize,
+    pub last_page_free_slots: usize,
+    pub pages: Vec<ShopPage>,
+}
+
+fn entry_title(key: &str, entry: &Value) -> String {
+    ["Title", "Description"]
+        .iter()
+        .filter_map(|field| entry.get(*field).and_then(Value::as_str))
+        .find(|text| !text.trim().is_empty())
+        .unwrap_or(key)
+        .to_string()
+}
+
+pub fn format_price(price: &Value) -> String {
+    match price.as_f64() {
+        Some(number) if number.fract() == 0.0 => format!("{}", number as i64),
+        Some(number) => number.to_string(),
+        None => price.to_string(),
+    }
+}
+
+fn render_shop_line(item_format: &str, dino_format: &str, index: usize, key: &str, entry: &Value) -> ShopLine {
+    let entry_type = entry.get("Type").and_then(Value::as_str).unwrap_or_default().to_string();
+    let price = entry.get("Price").cloned().unwrap_or(Value::Null);
+    let title = entry_title(key, entry);
+
+    let text = if entry_type == "dino" {
+        let level = entry.get("Level").map(|level| level.to_string()).unwrap_or_default();
+        format_placeholders(dino_format, &[index.to_string(), title, level, key.to_string(), format_price(&price)])
+    } else {
+        format_placeholders(item_format, &[index.to_string(), title, key.to_string(), format_price(&price)])
+    };
+
+    ShopLine {
+        index,
+        key: key.to_string(),
+        entry_type,
+        text: strip_rich_text(&text),
+        price: price.as_f64(),
+    }
+}
+
+pub fn preview_shop(config: &Value) -> ShopPreview {
+    let items_per_page = config
+        .pointer("/General/ItemsPerPage")
+        .and_then(Value::as_u64)
+        .unwrap_or(DEFAULT_ITEMS_PER_PAGE)
+        .max(1) as usize;
+
+    let item_format = message_text(config, "StoreListItem");
+    let dino_format = message_text(config, "StoreListDino");
+    let lines: Vec<ShopLine> = config
+        .get("ShopItems")
+        .and_then(Value::as_object)
+        .map(|entries| {
+            entries
+                .iter()
+                .enumerate()
+                .map(|(position, (key, entry))| render_shop_line(&item_format, &dino_format, position + 1, key, entry))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let total_entries = lines.len();
+    let list_format = message_text(config, "StoreListFormat");
+    let mut pages = Vec::new();
+    let mut remaining = lines.into_iter().peekable();
+
+    while remaining.peek().is_some() {
+        let page_lines: Vec<ShopLine> = remaining.by_ref().take(items_per_page).collect();
+        let joined: String = page_lines.iter().map(|line| line.text.as_str()).collect();
+        pages.push(ShopPage {
+            number: pages.len() + 1,
+            text: strip_rich_text(&format_placeholders(&list_format, &[joined])),
+            lines: page_lines,
+        });
+    }
+
+    let last_page_entries = pages.last().map_or(0, |page| page.lines.len());
+    ShopPreview {
+        items_per_page,
+        total_entries,
+        total_pages: pages.len(),
+        last_page_entries,
+        last_page_free_slots: if pages.is_empty() { 0 } else { items_per_page - last_page_entries },
+        pages,
+    }
+}
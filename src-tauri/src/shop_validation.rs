@@ -0,0 +1,413 @@
+// src-tauri/src/shop_validation.rs
+
+use std::collections::HashSet;
+use serde::Serialize;
+use serde_json::Value;
+use crate::ark_data::IssueSeverity;
+use crate::blueprint::parse_blueprint;
+use crate::config_refs::pointer;
+
+pub const ENTRY_TYPES: [&str; 6] = ["item", "dino", "beacon", "experience", "unlockengram", "command"];
+
+// Fields every entry type accepts
+pub const COMMON_FIELDS: [&str; 7] = ["Type", "Title", "Description", "Price", "MinLevel", "MaxLevel", "Permissions"];
+
+pub const MAX_QUALITY: f64 = 100.0;
+pub const MAX_DINO_LEVEL: i64 = 10000;
+pub const STRYDER_ATTACHMENTS: std::ops::RangeInclusive<i64> = -1..=3;
+pub const GENDERS: [&str; 3] = ["random", "male", "female"];
+
+#[derive(Serialize, Debug)]
+pub struct EntryIssue {
+    pub key: String,
+    pub entry_type: Option<String>,
+    pub severity: IssueSeverity,
+    pub pointer: String,
+    pub message: String,
+}
+
+// Canonical blueprints of the catalog's Dinos and Engrams
+pub struct CatalogBlueprints {
+    dinos: HashSet<String>,
+    engrams: HashSet<String>,
+}
+
+impl CatalogBlueprints {
+    pub fn from_catalog(catalog: &Value) -> Self {
+        let collect = |section: &str| -> HashSet<String> {
+            catalog
+                .get(section)
+                .and_then(Value::as_object)
+                .map(|entries| {
+                    entries
+                        .values()
+                        .filter_map(|entry| entry.get("Blueprint").and_then(Value::as_str))
+                        .filter_map(|blueprint| parse_blueprint(blueprint).ok())
+                        .map(|parsed| parsed.canonical())
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        CatalogBlueprints {
+            dinos: collect("Dinos"),
+            engrams: collect("Engrams"),
+        }
+    }
+}
+
+// Fields each type accepts on top of COMMON_FIELDS; the first list is required
+pub fn type_fields(entry_type: &str) -> (&'static [&'static str], &'static [&'static str]) {
+    match entry_type {
+        "item" => (&["Items"], &[]),
+        "dino" => (
+            &["Blueprint", "Level"],
+            &["Neutered", "Gender", "SaddleBlueprint", "PreventCryo", "GachaResources", "StryderHead", "StryderChest"],
+        ),
+        "beacon" => (&["ClassName"], &[]),
+        "experience" => (&["Amount"], &["GiveToDino"]),
+        "unlockengram" => (&["Items"], &[]),
+        "command" => (&["Items"], &[]),
+        _ => (&[], &[]),
+    }
+}
+
+fn field_path<'p>(path: &[&'p str], field: &'p str) -> Vec<&'p str> {
+    let mut segments = path.to_vec();
+    segments.push(field);
+    segments
+}
+
+struct EntryChecker<'a> {
+    key: &'a str,
+    entry_type: Option<String>,
+    issues: Vec<EntryIssue>,
+}
+
+impl<'a> EntryChecker<'a> {
+    fn push(&mut self, severity: IssueSeverity, path: &[&str], message: String) {
+        let mut segments = vec!["ShopItems", self.key];
+        segments.extend_from_slice(path);
+        self.issues.push(EntryIssue {
+            key: self.key.to_string(),
+            entry_type: self.entry_type.clone(),
+            severity,
+            pointer: pointer(&segments),
+            message,
+        });
+    }
+
+    fn error(&mut self, path: &[&str], message: String) {
+        self.push(IssueSeverity::Error, path, message);
+    }
+
+    fn integer(&mut self, value: &Value, path: &[&str], field: &str, min: i64, max: Option<i64>) {
+        match value.get(field) {
+            None => {}
+            Some(number) => match (number.as_i64(), max) {
+                (Some(number), _) if number >= min && max.map_or(true, |max| number <= max) => {}
+                (Some(number), Some(max)) => self.error(&field_path(path, field), format!("{} {} is outside {} - {}", field, number, min, max)),
+                (Some(_), None) => self.error(&field_path(path, field), format!("{} must be at least {}", field, min)),
+                (None, _) => self.error(&field_path(path, field), format!("{} must be a whole number", field)),
+            },
+        }
+    }
+
+    fn boolean(&mut self, value: &Value, path: &[&str], field: &str) {
+        if value.get(field).map_or(false, |flag| !flag.is_boolean()) {
+            self.error(&field_path(path, field), format!("{} must be true or false", field));
+        }
+    }
+
+    fn text(&mut self, value: &Value, path: &[&str], field: &str, required: bool) -> Option<String> {
+        match value.get(field) {
+            Some(Value::String(text)) if !text.trim().is_empty() => Some(text.clone()),
+            Some(Value::String(_)) if required => {
+                self.error(&field_path(path, field), format!("{} can't be empty", field));
+                None
+            }
+            // Missing top-level required fields are reported with the type's field list
+            None if required && !path.is_empty() => {
+                self.error(&field_path(path, field), format!("{} is required", field));
+                None
+            }
+            Some(Value::String(_)) | None => None,
+            Some(_) => {
+                self.error(&field_path(path, field), format!("{} must be text", field));
+                None
+            }
+        }
+    }
+
+    fn items<'v>(&mut self, entry: &'v Value) -> Vec<(String, &'v Value)> {
+        match entry.get("Items") {
+            Some(Value::Array(items)) if !items.is_empty() => {
+                items.iter().enumerate().map(|(index, item)| (index.to_string(), item)).collect()
+            }
+            Some(Value::Array(_)) => {
+                self.error(&["Items"], "Items must contain at least one entry".to_string());
+                Vec::new()
+            }
+            Some(_) => {
+                self.error(&["Items"], "Items must be a list".to_string());
+                Vec::new()
+            }
+            // Already reported as a missing required field
+            None => Vec::new(),
+        }
+    }
+}
+
+fn in_catalog(blueprint: &str, known: &HashSet<String>) -> bool {
+    // Unparseable blueprints are the blueprint validator's job
+    parse_blueprint(blueprint).map_or(true, |parsed| known.contains(&parsed.canonical()))
+}
+
+fn validate_entry(key: &str, entry: &Value, catalog: Option<&CatalogBlueprints>) -> Vec<EntryIssue> {
+    let mut checker = EntryChecker {
+        key,
+        entry_type: entry.get("Type").and_then(Value::as_str).map(str::to_string),
+        issues: Vec::new(),
+    };
+
+    let fields = match entry.as_object() {
+        Some(fields) => fields,
+        None => {
+            checker.error(&[], "Entry must be a JSON object".to_string());
+            return checker.issues;
+        }
+    };
+
+    let entry_type = match checker.entry_type.clone() {
+        Some(entry_type) if ENTRY_TYPES.contains(&entry_type.as_str()) => entry_type,
+        Some(entry_type) => {
+            checker.error(&["Type"], format!("Unknown Type \"{}\", expected one of: {}", entry_type, ENTRY_TYPES.join(", ")));
+            return checker.issues;
+        }
+        None => {
+            checker.error(&["Type"], "Type is required".to_string());
+            return checker.issues;
+        }
+    };
+
+    let (required, optional) = type_fields(&entry_type);
+    for field in required {
+        if !fields.contains_key(*field) {
+            checker.error(&[field], format!("{} is required for {} entries", field, entry_type));
+        }
+    }
+    for (field, value) in fields {
+        let field = field.as_str();
+        // The editor gives every new entry an empty Items list, which the plugin ignores
+        if field == "Items" && value.as_array().map_or(false, Vec::is_empty) {
+            continue;
+        }
+        if !COMMON_FIELDS.contains(&field) && !required.contains(&field) && !optional.contains(&field) {
+            checker.error(&[field], format!("{} isn't used by {} entries", field, entry_type));
+        }
+    }
+
+    if entry.get("Price").map_or(false, |price| price.as_f64().map_or(true, |price| price < 0.0)) {
+        checker.error(&["Price"], "Price must be a number of at least 0".to_string());
+    }
+    checker.integer(entry, &[], "MinLevel", 1, None);
+    checker.integer(entry, &[], "MaxLevel", 1, None);
+
+    match entry_type.as_str() {
+        "item" => {
+            for (index, item) in checker.items(entry) {
+                let path = ["Items", index.as_str()];
+                checker.text(item, &path, "Blueprint", true);
+                checker.integer(item, &path, "Amount", 1, None);
+                checker.boolean(item, &path, "ForceBlueprint");
+                if let Some(quality) = item.get("Quality") {
+                    match quality.as_f64() {
+                        Some(quality) if (0.0..=MAX_QUALITY).contains(&quality) => {}
+                        _ => checker.error(&field_path(&path, "Quality"), format!("Quality must be a number from 0 to {}", MAX_QUALITY)),
+                    }
+                }
+            }
+        }
+        "dino" => {
+            if let Some(blueprint) = checker.text(entry, &[], "Blueprint", true) {
+                if catalog.map_or(false, |catalog| !in_catalog(&blueprint, &catalog.dinos)) {
+                    checker.error(&["Blueprint"], format!("\"{}\" isn't in the Dinos catalog", blueprint));
+                }
+            }
+            checker.integer(entry, &[], "Level", 1, Some(MAX_DINO_LEVEL));
+            checker.boolean(entry, &[], "Neutered");
+            checker.boolean(entry, &[], "PreventCryo");
+            checker.text(entry, &[], "SaddleBlueprint", false);
+            if let Some(gender) = checker.text(entry, &[], "Gender", false) {
+                if !GENDERS.contains(&gender.to_lowercase().as_str()) {
+                    checker.error(&["Gender"], format!("Gender must be one of: {}", GENDERS.join(", ")));
+                }
+            }
+            for field in ["StryderHead", "StryderChest"] {
+                checker.integer(entry, &[], field, *STRYDER_ATTACHMENTS.start(), Some(*STRYDER_ATTACHMENTS.end()));
+            }
+            if entry.get("GachaResources").map_or(false, |resources| !resources.is_object()) {
+                checker.error(&["GachaResources"], "GachaResources must map blueprints to amounts".to_string());
+            }
+        }
+        "beacon" => {
+            checker.text(entry, &[], "ClassName", true);
+        }
+        "experience" => {
+            checker.integer(entry, &[], "Amount", 1, None);
+            checker.boolean(entry, &[], "GiveToDino");
+        }
+        "unlockengram" => {
+            for (index, item) in checker.items(entry) {
+                let path = ["Items", index.as_str()];
+                if let Some(blueprint) = checker.text(item, &path, "Blueprint", true) {
+                    if catalog.map_or(false, |catalog| !in_catalog(&blueprint, &catalog.engrams)) {
+                        checker.error(&field_path(&path, "Blueprint"), format!("\"{}\" isn't in the Engrams catalog", blueprint));
+                    }
+                }
+            }
+        }
+        "command" => {
+            for (index, item) in checker.items(entry) {
+                let path = ["Items", index.as_str()];
+                checker.text(item, &path, "Command", true);
+                checker.text(item, &path, "DisplayAs", false);
+                checker.boolean(item, &path, "ExecuteAsAdmin");
+            }
+        }
+        _ => {}
+    }
+
+    checker.issues
+}
+
+// Catalog checks are skipped when no catalog is available
+pub fn validate_shop_entries(config: &Value, catalog: Option<&CatalogBlueprints>) -> Vec<EntryIssue> {
+    config
+        .get("ShopItems")
+        .and_then(Value::as_object)
+        .map(|entries| {
+            entries
+                .iter()
+                .flat_map(|(key, entry)| validate_entry(key, entry, catalog))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const RAPTOR: &str = "Blueprint'/Game/PrimalEarth/Dinos/Raptor/Raptor_Character_BP.Raptor_Character_BP'";
+    const DODO: &str = "Blueprint'/Game/PrimalEarth/Dinos/Dodo/Dodo_Character_BP.Dodo_Character_BP'";
+    const FORGE: &str = "Blueprint'/Game/PrimalEarth/CoreBlueprints/Items/Structures/Misc/PrimalItemStructure_Forge.PrimalItemStructure_Forge'";
+    const SMITHY: &str = "Blueprint'/Game/PrimalEarth/CoreBlueprints/Items/Structures/Misc/PrimalItemStructure_AnvilBench.PrimalItemStructure_AnvilBench'";
+
+    fn catalog() -> CatalogBlueprints {
+        CatalogBlueprints::from_catalog(&json!({
+            "Dinos": { "Raptor": { "Blueprint": "/Game/PrimalEarth/Dinos/Raptor/Raptor_Character_BP.Raptor_Character_BP_C" } },
+            "Engrams": { "Forge": { "Blueprint": FORGE } }
+        }))
+    }
+
+    fn issues(entry: Value, catalog: Option<&CatalogBlueprints>) -> Vec<(String, String)> {
+        validate_shop_entries(&json!({ "ShopItems": { "entry": entry } }), catalog)
+            .into_iter()
+            .map(|issue| (issue.pointer, issue.message))
+            .collect()
+    }
+
+    fn issue(pointer: &str, message: &str) -> (String, String) {
+        (format!("/ShopItems/entry{}", pointer), message.to_string())
+    }
+
+    #[test]
+    fn valid_entries_of_every_type_pass() {
+        let entries = [
+            json!({ "Type": "item", "Price": 10, "Items": [{ "Blueprint": FORGE, "Amount": 1, "Quality": 100, "ForceBlueprint": false }] }),
+            json!({ "Type": "dino", "Price": 0, "Blueprint": RAPTOR, "Level": 10000, "Gender": "Female", "StryderHead": -1, "StryderChest": 3 }),
+            json!({ "Type": "beacon", "ClassName": "SupplyCrate_Level03_C", "Items": [] }),
+            json!({ "Type": "experience", "Amount": 500, "GiveToDino": true }),
+            json!({ "Type": "unlockengram", "Items": [{ "Blueprint": FORGE }] }),
+            json!({ "Type": "command", "Items": [{ "Command": "settimeofday 08:00", "DisplayAs": "Morning", "ExecuteAsAdmin": true }] }),
+        ];
+        for entry in entries {
+            assert_eq!(issues(entry.clone(), Some(&catalog())), Vec::new(), "{}", entry);
+        }
+    }
+
+    #[test]
+    fn reports_missing_required_and_forbidden_fields() {
+        let cases = [
+            (json!({ "Type": "item" }), vec![issue("/Items", "Items is required for item entries")]),
+            (json!({ "Type": "dino", "Level": 1 }), vec![issue("/Blueprint", "Blueprint is required for dino entries")]),
+            (json!({ "Type": "beacon", "ClassName": " " }), vec![issue("/ClassName", "ClassName can't be empty")]),
+            (json!({ "Type": "experience", "Amount": 1, "Level": 5 }), vec![issue("/Level", "Level isn't used by experience entries")]),
+            (
+                json!({ "Type": "command", "Blueprint": RAPTOR, "Items": [{ "DisplayAs": "x" }] }),
+                vec![issue("/Blueprint", "Blueprint isn't used by command entries"), issue("/Items/0/Command", "Command is required")],
+            ),
+            (json!({ "Type": "unlockengram", "Items": [] }), vec![issue("/Items", "Items must contain at least one entry")]),
+            (json!({ "Type": "item", "Items": {} }), vec![issue("/Items", "Items must be a list")]),
+        ];
+        for (entry, expected) in cases {
+            assert_eq!(issues(entry.clone(), None), expected, "{}", entry);
+        }
+    }
+
+    #[test]
+    fn checks_quality_level_and_stryder_ranges() {
+        let item = |quality: Value| json!({ "Type": "item", "Items": [{ "Blueprint": FORGE, "Quality": quality }] });
+        let dino = |field: &str, value: i64| {
+            let mut entry = json!({ "Type": "dino", "Blueprint": RAPTOR, "Level": 1 });
+            entry[field] = json!(value);
+            entry
+        };
+        let quality_error = issue("/Items/0/Quality", "Quality must be a number from 0 to 100");
+        assert_eq!(issues(item(json!(0)), None), Vec::new());
+        assert_eq!(issues(item(json!(100.5)), None), vec![quality_error.clone()]);
+        assert_eq!(issues(item(json!(-1)), None), vec![quality_error.clone()]);
+        assert_eq!(issues(item(json!("high")), None), vec![quality_error]);
+
+        assert_eq!(issues(dino("Level", 10000), None), Vec::new());
+        assert_eq!(issues(dino("Level", 0), None), vec![issue("/Level", "Level 0 is outside 1 - 10000")]);
+        assert_eq!(issues(dino("Level", 10001), None), vec![issue("/Level", "Level 10001 is outside 1 - 10000")]);
+        assert_eq!(issues(dino("StryderHead", -1), None), Vec::new());
+        assert_eq!(issues(dino("StryderHead", -2), None), vec![issue("/StryderHead", "StryderHead -2 is outside -1 - 3")]);
+        assert_eq!(issues(dino("StryderHead", 4), None), vec![issue("/StryderHead", "StryderHead 4 is outside -1 - 3")]);
+        assert_eq!(
+            issues(json!({ "Type": "dino", "Blueprint": RAPTOR, "Level": 1.5 }), None),
+            vec![issue("/Level", "Level must be a whole number")],
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_or_missing_types() {
+        assert_eq!(
+            issues(json!({ "Type": "weapon", "Anything": 1 }), None),
+            vec![issue("/Type", "Unknown Type \"weapon\", expected one of: item, dino, beacon, experience, unlockengram, command")],
+        );
+        assert_eq!(issues(json!({ "Type": "Item", "Items": [] }), None)[0].1, "Unknown Type \"Item\", expected one of: item, dino, beacon, experience, unlockengram, command");
+        assert_eq!(issues(json!({ "Price": 1 }), None), vec![issue("/Type", "Type is required")]);
+        assert_eq!(issues(json!("item"), None), vec![issue("", "Entry must be a JSON object")]);
+    }
+
+    #[test]
+    fn catalog_misses_are_only_checked_with_a_catalog() {
+        let dino = json!({ "Type": "dino", "Blueprint": DODO, "Level": 1 });
+        let engram = json!({ "Type": "unlockengram", "Items": [{ "Blueprint": FORGE }, { "Blueprint": SMITHY }] });
+
+        assert_eq!(issues(dino.clone(), None), Vec::new());
+        assert_eq!(issues(engram.clone(), None), Vec::new());
+
+        assert_eq!(issues(dino, Some(&catalog())), vec![issue("/Blueprint", &format!("\"{}\" isn't in the Dinos catalog", DODO))]);
+        assert_eq!(issues(engram, Some(&catalog())), vec![issue("/Items/1/Blueprint", &format!("\"{}\" isn't in the Engrams catalog", SMITHY))]);
+        // Spelled differently from the catalog but the same blueprint
+        let raptor = json!({ "Type": "dino", "Blueprint": "/Game/PrimalEarth/Dinos/Raptor/Raptor_Character_BP", "Level": 1 });
+        assert_eq!(issues(raptor, Some(&catalog())), Vec::new());
+        // A dino blueprint isn't an engram
+        let wrong_section = json!({ "Type": "unlockengram", "Items": [{ "Blueprint": RAPTOR }] });
+        assert_eq!(issues(wrong_section, Some(&catalog())).len(), 1);
+    }
+}
@@ -0,0 +1,523 @@
+// src-tauri/src/simulator.rs
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use crate::eligibility::{entry_groups, level_window, DEFAULT_GROUP};
+use crate::message_schema::{format_placeholders, message_text};
+use crate::rich_color::strip_rich_text;
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct SimPlayer {
+    pub points: i64,
+    pub level: i64,
+    #[serde(default)]
+    pub groups: Vec<String>,
+    // Blueprint -> stack count
+    #[serde(default)]
+    pub inventory: BTreeMap<String, i64>,
+    // Kit key -> uses left; kits not listed start at their DefaultAmount
+    #[serde(default)]
+    pub kits: BTreeMap<String, i64>,
+    // Dinos, beacons, experience, engrams and commands handed out so far
+    #[serde(default)]
+    pub received: Vec<String>,
+    // OnlyFromSpawn kits can only be redeemed while this is set
+    #[serde(default)]
+    pub just_spawned: bool,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Scenario {
+    pub player: SimPlayer,
+    pub steps: Vec<String>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct StepResult {
+    pub command: String,
+    pub success: bool,
+    pub message_key: String,
+    pub message: String,
+    // Extra lines the plugin prints after the message, e.g. the kit list
+    pub details: Vec<String>,
+    pub points: i64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ScenarioResult {
+    pub steps: Vec<StepResult>,
+    pub player: SimPlayer,
+}
+
+pub struct Simulator<'a> {
+    config: &'a Value,
+    pub player: SimPlayer,
+}
+
+struct Outcome {
+    success: bool,
+    key: &'static str,
+    args: Vec<String>,
+    details: Vec<String>,
+}
+
+fn ok(key: &'static str, args: Vec<String>) -> Outcome {
+    Outcome { success: true, key, args, details: Vec::new() }
+}
+
+fn fail(key: &'static str, args: Vec<String>) -> Outcome {
+    Outcome { success: false, key, args, details: Vec::new() }
+}
+
+fn number(entry: &Value, field: &str, default: i64) -> i64 {
+    entry
+        .get(field)
+        .and_then(|value| value.as_i64().or_else(|| value.as_f64().map(|f| f as i64)))
+        .unwrap_or(default)
+}
+
+fn entry_name(key: &str, entry: &Value) -> String {
+    entry
+        .get("Title")
+        .and_then(Value::as_str)
+        .filter(|title| !title.trim().is_empty())
+        .unwrap_or(key)
+        .to_string()
+}
+
+fn parse_amount(arg: Option<&&str>) -> Option<i64> {
+    match arg {
+        None => Some(1),
+        Some(raw) => raw.parse::<i64>().ok().filter(|amount| *amount > 0),
+    }
+}
+
+impl<'a> Simulator<'a> {
+    pub fn new(config: &'a Value, player: SimPlayer) -> Self {
+        Simulator { config, player }
+    }
+
+    // ArkApi matches group names case-insensitively
+    fn in_group(&self, group: &str) -> bool {
+        group.eq_ignore_ascii_case(DEFAULT_GROUP) || self.player.groups.iter().any(|own| own.eq_ignore_ascii_case(group))
+    }
+
+    fn has_permission(&self, entry: &Value) -> bool {
+        let groups = entry_groups(entry);
+        groups.is_empty() || groups.iter().any(|group| self.in_group(group))
+    }
+
+    fn level_problem(&self, entry: &Value) -> Option<Outcome> {
+        let (min, max) = level_window(entry);
+        if self.player.level < min || self.player.level > max {
+            Some(fail("BadLevel", vec![min.to_string(), max.to_string()]))
+        } else {
+            None
+        }
+    }
+
+    // None when a stack would overflow, in which case the inventory is left untouched
+    fn give_items(&mut self, items: Option<&Value>, multiplier: i64) -> Option<()> {
+        let mut inventory = self.player.inventory.clone();
+        for item in items.and_then(Value::as_array).into_iter().flatten() {
+            if let Some(blueprint) = item.get("Blueprint").and_then(Value::as_str) {
+                let amount = number(item, "Amount", 1).checked_mul(multiplier)?;
+                let stack = inventory.entry(blueprint.trim().to_string()).or_insert(0);
+                *stack = stack.checked_add(amount)?;
+            }
+        }
+        self.player.inventory = inventory;
+        Some(())
+    }
+
+    fn entry(&self, section: &str, key: &str) -> Option<&'a Value> {
+        self.config.get(section).and_then(|entries| entries.get(key))
+    }
+
+    pub fn run(&mut self, command: &str) -> StepResult {
+        let args: Vec<&str> = command.split_whitespace().collect();
+        let outcome = match args.first().map(|word| word.trim_start_matches('/').to_lowercase()).as_deref() {
+            Some("buy") => self.buy(&args[1..]),
+            Some("kit") => self.kit(&args[1..]),
+            Some("buykit") => self.buy_kit(&args[1..]),
+            Some("sell") => self.sell(&args[1..]),
+            _ => fail("WrongId", Vec::new()),
+        };
+
+        let template = message_text(self.config, outcome.key);
+        StepResult {
+            command: command.to_string(),
+            success: outcome.success,
+            message_key: outcome.key.to_string(),
+            message: strip_rich_text(&format_placeholders(&template, &outcome.args)),
+            details: outcome.details,
+            points: self.player.points,
+        }
+    }
+
+    fn buy(&mut self, args: &[&str]) -> Outcome {
+        let key = match args.first() {
+            Some(key) => *key,
+            None => return fail("BuyUsage", Vec::new()),
+        };
+        let amount = match parse_amount(args.get(1)) {
+            Some(amount) => amount,
+            None => return fail("BuyUsage", Vec::new()),
+        };
+        let entry = match self.entry("ShopItems", key) {
+            Some(entry) => entry,
+            None => return fail("WrongId", Vec::new()),
+        };
+
+        let entry_type = entry.get("Type").and_then(Value::as_str).unwrap_or_default().to_lowercase();
+        if let Some(problem) = self.level_problem(entry) {
+            return problem;
+        }
+        if !self.has_permission(entry) {
+            return fail("NoPermissionsStore", vec![entry_type]);
+        }
+
+        // Amounts come straight from the player, so overflowing totals are rejected like bad input
+        let cost = match number(entry, "Price", 0).checked_mul(amount) {
+            Some(cost) => cost,
+            None => return fail("BuyUsage", Vec::new()),
+        };
+        if cost > self.player.points {
+            return fail("NoPoints", Vec::new());
+        }
+        let points = match self.player.points.checked_sub(cost) {
+            Some(points) => points,
+            None => return fail("BuyUsage", Vec::new()),
+        };
+
+        let name = entry_name(key, entry);
+        let key_message = match entry_type.as_str() {
+            "item" => {
+                if self.give_items(entry.get("Items"), amount).is_none() {
+                    return fail("BuyUsage", Vec::new());
+                }
+                "BoughtItem"
+            }
+            "dino" => {
+                for _ in 0..amount {
+                    self.player.received.push(format!("dino: {}", name));
+                }
+                "BoughtDino"
+            }
+            "beacon" => {
+                for _ in 0..amount {
+                    self.player.received.push(format!("beacon: {}", name));
+                }
+                "BoughtBeacon"
+            }
+            "experience" => {
+                let experience = match number(entry, "Amount", 0).checked_mul(amount) {
+                    Some(experience) => experience,
+                    None => return fail("BuyUsage", Vec::new()),
+                };
+                self.player.received.push(format!("experience: {}", experience));
+                "BoughtExp"
+            }
+            "unlockengram" => {
+                for item in entry.get("Items").and_then(Value::as_array).into_iter().flatten() {
+                    if let Some(blueprint) = item.get("Blueprint").and_then(Value::as_str) {
+                        self.player.received.push(format!("engram: {}", blueprint));
+                    }
+                }
+                "BoughtItem"
+            }
+            "command" => {
+                for item in entry.get("Items").and_then(Value::as_array).into_iter().flatten() {
+                    if let Some(command) = item.get("Command").and_then(Value::as_str) {
+                        for _ in 0..amount {
+                            self.player.received.push(format!("command: {}", command));
+                        }
+                    }
+                }
+                "BoughtItem"
+            }
+            _ => return fail("WrongId", Vec::new()),
+        };
+
+        self.player.points = points;
+        ok(key_message, Vec::new())
+    }
+
+    fn kit_uses_left(&self, key: &str, kit: &Value) -> i64 {
+        self.player
+            .kits
+            .get(key)
+            .copied()
+            .unwrap_or_else(|| number(kit, "DefaultAmount", 0))
+    }
+
+    fn available_kits(&self) -> Vec<String> {
+        self.config
+            .get("Kits")
+            .and_then(Value::as_object)
+            .map(|kits| {
+                kits.iter()
+                    .filter(|(_, kit)| self.has_permission(kit) && self.level_problem(kit).is_none())
+                    .map(|(key, _)| key.clone())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    fn kit(&mut self, args: &[&str]) -> Outcome {
+        let key = match args.first() {
+            Some(key) => *key,
+            None => {
+                let kits = self.available_kits();
+                return if kits.is_empty() {
+                    ok("NoKits", Vec::new())
+                } else {
+                    Outcome { details: kits, ..ok("AvailableKits", Vec::new()) }
+                };
+            }
+        };
+        let kit = match self.entry("Kits", key) {
+            Some(kit) => kit,
+            None => return fail("WrongId", Vec::new()),
+        };
+
+        if !self.has_permission(kit) {
+            return fail("NoPermissionsKit", Vec::new());
+        }
+        if let Some(problem) = self.level_problem(kit) {
+            return problem;
+        }
+
+        if kit.get("OnlyFromSpawn").and_then(Value::as_bool).unwrap_or(false) && !self.player.just_spawned {
+            return fail("OnlyOnSpawnKit", Vec::new());
+        }
+
+        let left = self.kit_uses_left(key, kit);
+        if left <= 0 {
+            return fail("NoKitsLeft", vec![key.to_string()]);
+        }
+
+        if self.give_items(kit.get("Items"), 1).is_none() {
+            return fail("KitUsage", Vec::new());
+        }
+        for dino in kit.get("Dinos").and_then(Value::as_array).into_iter().flatten() {
+            if let Some(blueprint) = dino.get("Blueprint").and_then(Value::as_str) {
+                self.player.received.push(format!("dino: {}", blueprint));
+            }
+        }
+        self.player.kits.insert(key.to_string(), left - 1);
+        ok("KitsLeft", vec![(left - 1).to_string(), key.to_string()])
+    }
+
+    fn buy_kit(&mut self, args: &[&str]) -> Outcome {
+        let key = match args.first() {
+            Some(key) => *key,
+            None => return fail("BuyKitUsage", Vec::new()),
+        };
+        let amount = match parse_amount(args.get(1)) {
+            Some(amount) => amount,
+            None => return fail("BuyKitUsage", Vec::new()),
+        };
+        let kit = match self.entry("Kits", key) {
+            Some(kit) => kit,
+            None => return fail("WrongId", Vec::new()),
+        };
+
+        if !self.has_permission(kit) {
+            return fail("NoPermissionsKit", Vec::new());
+        }
+        if let Some(problem) = self.level_problem(kit) {
+            return problem;
+        }
+
+        let price = number(kit, "Price", 0);
+        if price <= 0 {
+            return fail("CantBuyKit", Vec::new());
+        }
+        let cost = match price.checked_mul(amount) {
+            Some(cost) => cost,
+            None => return fail("BuyKitUsage", Vec::new()),
+        };
+        if cost > self.player.points {
+            return fail("NoPoints", Vec::new());
+        }
+
+        let left = match self.kit_uses_left(key, kit).checked_add(amount) {
+            Some(left) => left,
+            None => return fail("BuyKitUsage", Vec::new()),
+        };
+        self.player.kits.insert(key.to_string(), left);
+        self.player.points -= cost;
+        ok("BoughtKit", vec![key.to_string()])
+    }
+
+    fn sell(&mut self, args: &[&str]) -> Outcome {
+        let key = match args.first() {
+            Some(key) => *key,
+            None => return fail("SellUsage", Vec::new()),
+        };
+        let amount = match parse_amount(args.get(1)) {
+            Some(amount) => amount,
+            None => return fail("SellUsage", Vec::new()),
+        };
+        let entry = match self.entry("SellItems", key) {
+            Some(entry) => entry,
+            None => return fail("WrongId", Vec::new()),
+        };
+
+        let blueprint = entry.get("Blueprint").and_then(Value::as_str).unwrap_or_default().trim().to_string();
+        let needed = match number(entry, "Amount", 1).checked_mul(amount) {
+            Some(needed) => needed,
+            None => return fail("SellUsage", Vec::new()),
+        };
+        let points = match number(entry, "Price", 0).checked_mul(amount).and_then(|earned| self.player.points.checked_add(earned)) {
+            Some(points) => points,
+            None => return fail("SellUsage", Vec::new()),
+        };
+        let have = self.player.inventory.get(&blueprint).copied().unwrap_or(0);
+        if have < needed {
+            return fail("NotEnoughItems", vec![have.to_string(), needed.to_string()]);
+        }
+
+        if have == needed {
+            self.player.inventory.remove(&blueprint);
+        } else {
+            self.player.inventory.insert(blueprint, have - needed);
+        }
+        self.player.points = points;
+        ok("SoldItems", Vec::new())
+    }
+}
+
+pub fn run_scenario(config: &Value, scenario: Scenario) -> ScenarioResult {
+    let mut simulator = Simulator::new(config, scenario.player);
+    let steps = scenario.steps.iter().map(|step| simulator.run(step)).collect();
+    ScenarioResult {
+        steps,
+        player: simulator.player,
+    }
+}
+
+// Entry point for `--simulate <config.json> <scenario.json>`
+pub fn run_scenario_files(config_path: &Path, scenario_path: &Path) -> Result<ScenarioResult, String> {
+    let config: Value = serde_json::from_str(
+        &fs::read_to_string(config_path).map_err(|e| format!("Error reading config: {}", e))?,
+    )
+    .map_err(|e| format!("Error parsing config: {}", e))?;
+    let scenario: Scenario = serde_json::from_str(
+        &fs::read_to_string(scenario_path).map_err(|e| format!("Error reading scenario: {}", e))?,
+    )
+    .map_err(|e| format!("Error parsing scenario: {}", e))?;
+
+    Ok(run_scenario(&config, scenario))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn config() -> Value {
+        json!({
+            "ShopItems": {
+                "ammo": {
+                    "Type": "item",
+                    "Price": 10,
+                    "Permissions": "default",
+                    "Items": [{ "Blueprint": "Blueprint'/Game/Ammo.Ammo'", "Amount": 50 }]
+                },
+                "rex": { "Type": "dino", "Price": 100, "Permissions": "VIP", "MinLevel": 1, "MaxLevel": 150 },
+                "elder": { "Type": "item", "Price": 5, "MinLevel": 100, "Items": [] }
+            },
+            "Kits": {
+                "starter": {
+                    "DefaultAmount": 1,
+                    "Price": 20,
+                    "Items": [{ "Blueprint": "Blueprint'/Game/Pick.Pick'", "Amount": 1 }]
+                }
+            },
+            "SellItems": {
+                "ammo": { "Type": "item", "Price": 3, "Amount": 50, "Blueprint": "Blueprint'/Game/Ammo.Ammo'" }
+            }
+        })
+    }
+
+    fn scenario(points: i64, level: i64, groups: &[&str], steps: &[&str]) -> Scenario {
+        Scenario {
+            player: SimPlayer {
+                points,
+                level,
+                groups: groups.iter().map(|group| group.to_string()).collect(),
+                ..SimPlayer::default()
+            },
+            steps: steps.iter().map(|step| step.to_string()).collect(),
+        }
+    }
+
+    fn outcomes(result: &ScenarioResult) -> Vec<(bool, &str, i64)> {
+        result.steps.iter().map(|step| (step.success, step.message_key.as_str(), step.points)).collect()
+    }
+
+    #[test]
+    fn buys_and_sells_items() {
+        let result = run_scenario(&config(), scenario(100, 10, &[], &["/buy ammo 2", "/sell ammo", "/sell ammo 2"]));
+        assert_eq!(
+            outcomes(&result),
+            vec![(true, "BoughtItem", 80), (true, "SoldItems", 83), (false, "NotEnoughItems", 83)]
+        );
+        assert_eq!(result.player.inventory.get("Blueprint'/Game/Ammo.Ammo'"), Some(&50));
+    }
+
+    #[test]
+    fn rejects_purchases_without_enough_points() {
+        let result = run_scenario(&config(), scenario(15, 10, &["Vip"], &["/buy ammo 2", "/buy rex"]));
+        assert_eq!(outcomes(&result), vec![(false, "NoPoints", 15), (false, "NoPoints", 15)]);
+        assert!(result.player.inventory.is_empty());
+    }
+
+    #[test]
+    fn limits_kit_uses_until_more_are_bought() {
+        let result = run_scenario(&config(), scenario(50, 10, &[], &["/kit starter", "/kit starter", "/buykit starter 2", "/kit starter"]));
+        assert_eq!(
+            outcomes(&result),
+            vec![(true, "KitsLeft", 50), (false, "NoKitsLeft", 50), (true, "BoughtKit", 10), (true, "KitsLeft", 10)]
+        );
+        assert_eq!(result.player.kits.get("starter"), Some(&1));
+        assert_eq!(result.player.inventory.get("Blueprint'/Game/Pick.Pick'"), Some(&2));
+    }
+
+    #[test]
+    fn enforces_the_level_window() {
+        let result = run_scenario(&config(), scenario(100, 99, &[], &["/buy elder"]));
+        assert_eq!(outcomes(&result), vec![(false, "BadLevel", 100)]);
+
+        let result = run_scenario(&config(), scenario(100, 100, &[], &["/buy elder"]));
+        assert_eq!(outcomes(&result), vec![(true, "BoughtItem", 95)]);
+    }
+
+    #[test]
+    fn gates_entries_by_group_case_insensitively() {
+        let result = run_scenario(&config(), scenario(500, 10, &[], &["/buy rex"]));
+        assert_eq!(outcomes(&result), vec![(false, "NoPermissionsStore", 500)]);
+
+        let result = run_scenario(&config(), scenario(500, 10, &["vip"], &["/buy rex"]));
+        assert_eq!(outcomes(&result), vec![(true, "BoughtDino", 400)]);
+        assert_eq!(result.player.received, vec!["dino: rex"]);
+    }
+
+    #[test]
+    fn rejects_overflowing_amounts() {
+        let huge = format!("{}", i64::MAX / 2);
+        let result = run_scenario(
+            &config(),
+            scenario(100, 10, &[], &[&format!("/buy ammo {}", huge), &format!("/buykit starter {}", i64::MAX), &format!("/sell ammo {}", huge)]),
+        );
+        assert_eq!(
+            outcomes(&result),
+            vec![(false, "BuyUsage", 100), (false, "BuyKitUsage", 100), (false, "SellUsage", 100)]
+        );
+    }
+}
@@ -0,0 +1,607 @@
+// src-tauri/src/spreadsheet.rs
+
+use std::collections::HashMap;
+use std::path::Path;
+use calamine::{open_workbook_auto, Data, Reader};
+use rust_xlsxwriter::Workbook;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use crate::blueprint::parse_blueprint;
+use crate::config_refs::ENTRY_SECTIONS;
+use crate::shop_validation::ENTRY_TYPES;
+
+// Column order is part of the sheet format; only ever append new columns
+pub const COLUMNS: [&str; 21] = [
+    "Section", "Key", "Line", "LineKind", "Type", "Title", "Description", "Price", "MinLevel", "MaxLevel",
+    "Permissions", "DefaultAmount", "Amount", "Quality", "ForceBlueprint", "Blueprint", "Level", "ClassName",
+    "Command", "DisplayAs", "ExecuteAsAdmin",
+];
+
+// Entry-level columns, written on the row with an empty Line
+const ENTRY_COLUMNS: [&str; 12] = [
+    "Type", "Title", "Description", "Price", "MinLevel", "MaxLevel", "Permissions", "DefaultAmount", "Amount",
+    "Blueprint", "Level", "ClassName",
+];
+
+// Columns for one Items/Dinos line
+const LINE_COLUMNS: [&str; 8] = ["Amount", "Quality", "ForceBlueprint", "Blueprint", "Level", "Command", "DisplayAs", "ExecuteAsAdmin"];
+
+const NUMBER_COLUMNS: [&str; 8] = ["Price", "MinLevel", "MaxLevel", "DefaultAmount", "Amount", "Quality", "Level", "Line"];
+const BOOL_COLUMNS: [&str; 2] = ["ForceBlueprint", "ExecuteAsAdmin"];
+
+#[derive(Serialize, Debug)]
+pub struct RowError {
+    // 1-based row in the sheet, counting the header
+    pub row: usize,
+    pub column: Option<String>,
+    pub key: Option<String>,
+    pub message: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct EntryChange {
+    pub section: String,
+    pub key: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct SheetDiff {
+    pub added: Vec<EntryChange>,
+    pub changed: Vec<EntryChange>,
+    pub removed: Vec<EntryChange>,
+}
+
+#[derive(Serialize, Debug)]
+pub struct SheetImport {
+    pub diff: SheetDiff,
+    pub errors: Vec<RowError>,
+}
+
+fn cell(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(text)) => text.clone(),
+        Some(other) => other.to_string(),
+    }
+}
+
+fn row_for(section: &str, key: &str, line: Option<(usize, &str)>, source: &Value, fields: &[&str]) -> Vec<String> {
+    COLUMNS
+        .iter()
+        .map(|column| match *column {
+            "Section" => section.to_string(),
+            "Key" => key.to_string(),
+            "Line" => line.map(|(index, _)| (index + 1).to_string()).unwrap_or_default(),
+            "LineKind" => line.map(|(_, kind)| kind.to_string()).unwrap_or_default(),
+            column if fields.contains(&column) => cell(source.get(column)),
+            _ => String::new(),
+        })
+        .collect()
+}
+
+// Header plus one row per entry and one per Items/Dinos line
+pub fn sheet_rows(config: &Value) -> Vec<Vec<String>> {
+    let mut rows = vec![COLUMNS.iter().map(|column| column.to_string()).collect()];
+
+    for section in ENTRY_SECTIONS.iter() {
+        let entries = match config.get(*section).and_then(Value::as_object) {
+            Some(entries) => entries,
+            None => continue,
+        };
+        for (key, entry) in entries {
+            rows.push(row_for(section, key, None, entry, &ENTRY_COLUMNS));
+            for kind in ["Items", "Dinos"] {
+                for (index, line) in entry.get(kind).and_then(Value::as_array).into_iter().flatten().enumerate() {
+                    let line_kind = if kind == "Items" { "item" } else { "dino" };
+                    rows.push(row_for(section, key, Some((index, line_kind)), line, &LINE_COLUMNS));
+                }
+            }
+        }
+    }
+
+    rows
+}
+
+pub fn write_csv(path: &Path, rows: &[Vec<String>]) -> Result<(), String> {
+    let mut writer = csv::Writer::from_path(path).map_err(|e| format!("Error creating CSV: {}", e))?;
+    for row in rows {
+        writer.write_record(row).map_err(|e| format!("Error writing CSV: {}", e))?;
+    }
+    writer.flush().map_err(|e| format!("Error writing CSV: {}", e))
+}
+
+pub fn write_xlsx(path: &Path, rows: &[Vec<String>]) -> Result<(), String> {
+    let mut workbook = Workbook::new();
+    let sheet = workbook.add_worksheet();
+    for (row_index, row) in rows.iter().enumerate() {
+        for (column_index, value) in row.iter().enumerate() {
+            let (row_number, column_number) = (row_index as u32, column_index as u16);
+            // Numeric columns stay numbers so designers can use formulas on them
+            let number = value.parse::<f64>().ok().filter(|_| row_index > 0 && NUMBER_COLUMNS.contains(&COLUMNS[column_index]));
+            match number {
+                Some(number) => sheet.write_number(row_number, column_number, number),
+                None => sheet.write_string(row_number, column_number, value),
+            }
+            .map_err(|e| format!("Error writing spreadsheet: {}", e))?;
+        }
+    }
+    workbook.save(path).map_err(|e| format!("Error saving spreadsheet: {}", e))
+}
+
+pub fn read_csv(path: &Path) -> Result<Vec<Vec<String>>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_path(path)
+        .map_err(|e| format!("Error reading CSV: {}", e))?;
+    reader
+        .records()
+        .map(|record| {
+            record
+                .map(|record| record.iter().map(str::to_string).collect())
+                .map_err(|e| format!("Error reading CSV: {}", e))
+        })
+        .collect()
+}
+
+fn data_text(data: &Data) -> String {
+    match data {
+        Data::Empty => String::new(),
+        Data::Float(number) if number.fract() == 0.0 => format!("{}", *number as i64),
+        Data::Bool(flag) => flag.to_string(),
+        other => other.to_string(),
+    }
+}
+
+pub fn read_xlsx(path: &Path) -> Result<Vec<Vec<String>>, String> {
+    let mut workbook = open_workbook_auto(path).map_err(|e| format!("Error opening spreadsheet: {}", e))?;
+    let range = workbook
+        .worksheet_range_at(0)
+        .ok_or_else(|| "Spreadsheet has no worksheets".to_string())?
+        .map_err(|e| format!("Error reading spreadsheet: {}", e))?;
+    Ok(range.rows().map(|row| row.iter().map(data_text).collect()).collect())
+}
+
+pub fn read_sheet(path: &Path) -> Result<Vec<Vec<String>>, String> {
+    let is_csv = path.extension().and_then(|ext| ext.to_str()).map_or(false, |ext| ext.eq_ignore_ascii_case("csv"));
+    if is_csv { read_csv(path) } else { read_xlsx(path) }
+}
+
+fn number_value(text: &str) -> Option<Value> {
+    if let Ok(number) = text.parse::<i64>() {
+        return Some(Value::from(number));
+    }
+    text.parse::<f64>().ok().filter(|number| number.is_finite()).map(Value::from)
+}
+
+struct SheetRow {
+    row: usize,
+    cells: HashMap<&'static str, String>,
+}
+
+impl SheetRow {
+    fn get(&self, column: &str) -> &str {
+        self.cells.get(column).map(String::as_str).unwrap_or_default()
+    }
+}
+
+struct RowParser<'a> {
+    errors: &'a mut Vec<RowError>,
+    row: usize,
+    key: String,
+    failed: bool,
+}
+
+impl<'a> RowParser<'a> {
+    fn fail(&mut self, column: &str, message: String) {
+        self.failed = true;
+        self.errors.push(RowError {
+            row: self.row,
+            column: Some(column.to_string()),
+            key: Some(self.key.clone()),
+            message,
+        });
+    }
+
+    // Writes the named columns onto `target`; empty cells remove the field
+    // unless it was already an empty string
+    fn apply(&mut self, row: &SheetRow, columns: &[&str], target: &mut Map<String, Value>) {
+        for column in columns {
+            let text = row.get(column).trim();
+            if text.is_empty() {
+                target.retain(|field, value| field != column || value.as_str() == Some(""));
+                continue;
+            }
+
+            let value = if NUMBER_COLUMNS.contains(column) {
+                match number_value(text) {
+                    Some(Value::Number(number)) if *column == "Price" && number.as_f64().map_or(false, |price| price < 0.0) => {
+                        self.fail(column, format!("Price {} can't be negative", text));
+                        continue;
+                    }
+                    Some(value) => value,
+                    None => {
+                        self.fail(column, format!("\"{}\" isn't a number", text));
+                        continue;
+                    }
+                }
+            } else if BOOL_COLUMNS.contains(column) {
+                match text.to_lowercase().as_str() {
+                    "true" | "1" | "yes" => Value::Bool(true),
+                    "false" | "0" | "no" => Value::Bool(false),
+                    _ => {
+                        self.fail(column, format!("\"{}\" isn't true or false", text));
+                        continue;
+                    }
+                }
+            } else if *column == "Blueprint" {
+                match parse_blueprint(text) {
+                    Ok(parsed) => {
+                        // A cell naming the blueprint already there keeps its spelling, so an
+                        // untouched row isn't reported as a change
+                        let canonical = parsed.canonical();
+                        let current = target.get(*column).and_then(Value::as_str).and_then(|current| parse_blueprint(current).ok());
+                        if current.map_or(false, |current| current.canonical() == canonical) {
+                            continue;
+                        }
+                        Value::String(canonical)
+                    }
+                    Err(err) => {
+                        self.fail(column, err.message);
+                        continue;
+                    }
+                }
+            } else {
+                Value::String(text.to_string())
+            };
+            target.insert(column.to_string(), value);
+        }
+    }
+}
+
+struct SheetEntry {
+    value: Map<String, Value>,
+    lines: Vec<(usize, String, Value)>,
+    failed: bool,
+}
+
+// Rebuilds one entry from its sheet rows, on top of the existing entry so that
+// fields without a column (GachaResources, Neutered, ...) survive the round-trip
+fn build_entry(section: &str, key: &str, rows: &[SheetRow], existing: Option<&Value>, errors: &mut Vec<RowError>) -> Option<Value> {
+    let mut entry: Option<SheetEntry> = None;
+    let mut failed = false;
+
+    for row in rows {
+        let mut parser = RowParser { errors, row: row.row, key: key.to_string(), failed: false };
+        let line = row.get("Line").trim();
+
+        if line.is_empty() {
+            if entry.is_some() {
+                parser.fail("Key", format!("\"{}\" has more than one entry row", key));
+                failed = true;
+                continue;
+            }
+            let mut value = existing.and_then(Value::as_object).cloned().unwrap_or_default();
+            parser.apply(row, &ENTRY_COLUMNS, &mut value);
+
+            let entry_type = value.get("Type").and_then(Value::as_str).unwrap_or_default().to_string();
+            match section {
+                "ShopItems" if !ENTRY_TYPES.contains(&entry_type.as_str()) => {
+                    parser.fail("Type", format!("Unknown Type \"{}\", expected one of: {}", entry_type, ENTRY_TYPES.join(", ")));
+                }
+                "SellItems" if entry_type != "item" => parser.fail("Type", "SellItems entries must have Type \"item\"".to_string()),
+                _ => {}
+            }
+            entry = Some(SheetEntry { value, lines: Vec::new(), failed: parser.failed });
+            continue;
+        }
+
+        let index = match line.parse::<usize>() {
+            Ok(index) if index > 0 => index - 1,
+            _ => {
+                parser.fail("Line", format!("\"{}\" isn't a line number", line));
+                failed = true;
+                continue;
+            }
+        };
+        let kind = match row.get("LineKind").trim().to_lowercase().as_str() {
+            "" | "item" => "Items",
+            "dino" if section == "Kits" => "Dinos",
+            other => {
+                parser.fail("LineKind", format!("\"{}\" isn't a line kind for {}", other, section));
+                failed = true;
+                continue;
+            }
+        };
+
+        let mut line_value = existing
+            .and_then(|entry| entry.get(kind))
+            .and_then(|lines| lines.get(index))
+            .and_then(Value::as_object)
+            .cloned()
+            .unwrap_or_default();
+        parser.apply(row, &LINE_COLUMNS, &mut line_value);
+        failed |= parser.failed;
+
+        match entry.as_mut() {
+            Some(entry) => entry.lines.push((index, kind.to_string(), Value::Object(line_value))),
+            None => {
+                errors.push(RowError {
+                    row: row.row,
+                    column: Some("Line".to_string()),
+                    key: Some(key.to_string()),
+                    message: format!("Line rows for \"{}\" must come after its entry row", key),
+                });
+                failed = true;
+            }
+        }
+    }
+
+    let mut entry = entry?;
+    if failed || entry.failed {
+        return None;
+    }
+
+    entry.lines.sort_by_key(|(index, _, _)| *index);
+    let mut lists: Vec<(String, Vec<Value>)> = Vec::new();
+    for (_, kind, line) in entry.lines {
+        match lists.iter_mut().find(|(existing, _)| *existing == kind) {
+            Some((_, list)) => list.push(line),
+            None => lists.push((kind, vec![line])),
+        }
+    }
+
+    // Lists the sheet has no lines for are emptied if the entry used them
+    for kind in ["Items", "Dinos"] {
+        let list = lists.iter().position(|(existing, _)| existing == kind).map(|position| lists.remove(position).1);
+        match list {
+            Some(list) => {
+                entry.value.insert(kind.to_string(), Value::Array(list));
+            }
+            None if entry.value.contains_key(kind) => {
+                entry.value.insert(kind.to_string(), Value::Array(Vec::new()));
+            }
+            None => {}
+        }
+    }
+
+    Some(Value::Object(entry.value))
+}
+
+pub fn import_sheet(config: &Value, rows: &[Vec<String>]) -> SheetImport {
+    let mut errors = Vec::new();
+    let mut diff = SheetDiff::default();
+
+    let header = match rows.first() {
+        Some(header) => header,
+        None => {
+            errors.push(RowError { row: 1, column: None, key: None, message: "Sheet is empty".to_string() });
+            return SheetImport { diff, errors };
+        }
+    };
+    let positions: HashMap<&'static str, usize> = COLUMNS
+        .iter()
+        .filter_map(|column| header.iter().position(|name| name.trim() == *column).map(|position| (*column, position)))
+        .collect();
+    for required in ["Section", "Key"] {
+        if !positions.contains_key(required) {
+            errors.push(RowError { row: 1, column: Some(required.to_string()), key: None, message: format!("Missing the {} column", required) });
+        }
+    }
+    if !errors.is_empty() {
+        return SheetImport { diff, errors };
+    }
+
+    // Group rows by section and key, keeping sheet order
+    let mut grouped: Vec<(String, String, Vec<SheetRow>)> = Vec::new();
+    for (index, cells) in rows.iter().enumerate().skip(1) {
+        let row = SheetRow {
+            row: index + 1,
+            cells: positions.iter().map(|(column, position)| (*column, cells.get(*position).cloned().unwrap_or_default())).collect(),
+        };
+        if row.cells.values().all(|cell| cell.trim().is_empty()) {
+            continue;
+        }
+
+        let section = row.get("Section").trim().to_string();
+        let key = row.get("Key").trim().to_string();
+        if !ENTRY_SECTIONS.contains(&section.as_str()) {
+            errors.push(RowError {
+                row: row.row,
+                column: Some("Section".to_string()),
+                key: Some(key),
+                message: format!("\"{}\" isn't one of: {}", section, ENTRY_SECTIONS.join(", ")),
+            });
+            continue;
+        }
+        if key.is_empty() {
+            errors.push(RowError { row: row.row, column: Some("Key".to_string()), key: None, message: "Key is empty".to_string() });
+            continue;
+        }
+
+        match grouped.iter_mut().find(|(existing_section, existing_key, _)| *existing_section == section && *existing_key == key) {
+            Some((_, _, group)) => group.push(row),
+            None => grouped.push((section, key, vec![row])),
+        }
+    }
+
+    let mut seen: Vec<(String, String)> = Vec::new();
+    for (section, key, group) in &grouped {
+        let existing = config.get(section).and_then(|entries| entries.get(key));
+        seen.push((section.clone(), key.clone()));
+
+        // Entries with row errors are left as they are
+        let after = match build_entry(section, key, group, existing, &mut errors) {
+            Some(after) => after,
+            None => continue,
+        };
+        match existing {
+            None => diff.added.push(EntryChange { section: section.clone(), key: key.clone(), before: None, after: Some(after) }),
+            Some(before) if *before != after => diff.changed.push(EntryChange {
+                section: section.clone(),
+                key: key.clone(),
+                before: Some(before.clone()),
+                after: Some(after),
+            }),
+            Some(_) => {}
+        }
+    }
+
+    for section in ENTRY_SECTIONS.iter() {
+        for (key, before) in config.get(*section).and_then(Value::as_object).into_iter().flatten() {
+            if !seen.iter().any(|(seen_section, seen_key)| seen_section == section && seen_key == key) {
+                diff.removed.push(EntryChange { section: section.to_string(), key: key.clone(), before: Some(before.clone()), after: None });
+            }
+        }
+    }
+
+    SheetImport { diff, errors }
+}
+
+pub fn apply_sheet_diff(config: &mut Value, diff: &SheetDiff) -> Result<(), String> {
+    let root = config.as_object_mut().ok_or_else(|| "Config must be a JSON object".to_string())?;
+
+    for change in diff.changed.iter().chain(diff.added.iter()) {
+        let after = change.after.clone().ok_or_else(|| format!("No new value for {} \"{}\"", change.section, change.key))?;
+        let entries = root
+            .entry(change.section.clone())
+            .or_insert_with(|| Value::Object(Map::new()))
+            .as_object_mut()
+            .ok_or_else(|| format!("{} must be a JSON object", change.section))?;
+        entries.insert(change.key.clone(), after);
+    }
+
+    for change in &diff.removed {
+        if let Some(entries) = root.get_mut(&change.section).and_then(Value::as_object_mut) {
+            entries.retain(|key, _| *key != change.key);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn config() -> Value {
+        json!({
+            "ShopItems": {
+                "metal": {
+                    "Type": "item",
+                    "Title": "Metal",
+                    "Price": 25,
+                    "Items": [
+                        { "Amount": 100, "Quality": 0, "ForceBlueprint": false, "Blueprint": "Blueprint'/Game/PrimalEarth/CoreBlueprints/Resources/PrimalItemResource_MetalIngot.PrimalItemResource_MetalIngot'" }
+                    ]
+                },
+                "raptor": {
+                    "Type": "dino",
+                    "Price": 1.5,
+                    "Level": 150,
+                    "Neutered": true,
+                    "Blueprint": "/Game/PrimalEarth/Dinos/Raptor/Raptor_Character_BP.Raptor_Character_BP_C"
+                },
+                "day": { "Type": "command", "Price": 10, "Description": "", "Items": [{ "Command": "settimeofday 08:00", "ExecuteAsAdmin": true }] }
+            },
+            "Kits": {
+                "starter": {
+                    "DefaultAmount": 1,
+                    "Permissions": "Default",
+                    "Dinos": [{ "Level": 20, "Blueprint": "Blueprint'/Game/PrimalEarth/Dinos/Dodo/Dodo_Character_BP.Dodo_Character_BP'" }]
+                }
+            },
+            "SellItems": {
+                "stone": { "Type": "item", "Price": 2, "Amount": 100, "Blueprint": "Blueprint'/Game/PrimalEarth/CoreBlueprints/Resources/PrimalItemResource_Stone.PrimalItemResource_Stone'" }
+            }
+        })
+    }
+
+    fn set_cell(rows: &mut [Vec<String>], key: &str, line: &str, column: &str, text: &str) -> usize {
+        let position = |name: &str| COLUMNS.iter().position(|column| *column == name).unwrap();
+        let index = rows
+            .iter()
+            .position(|row| row[position("Key")] == key && row[position("Line")] == line)
+            .unwrap();
+        rows[index][position(column)] = text.to_string();
+        // 1-based, counting the header
+        index + 1
+    }
+
+    #[test]
+    fn round_trip_is_an_empty_diff() {
+        let config = config();
+        let import = import_sheet(&config, &sheet_rows(&config));
+        assert!(import.errors.is_empty(), "{:?}", import.errors);
+        assert!(import.diff.added.is_empty(), "{:?}", import.diff.added);
+        assert!(import.diff.changed.is_empty(), "{:?}", import.diff.changed);
+        assert!(import.diff.removed.is_empty(), "{:?}", import.diff.removed);
+    }
+
+    #[test]
+    fn edited_blueprints_are_written_in_canonical_form() {
+        let config = config();
+        let mut rows = sheet_rows(&config);
+        set_cell(&mut rows, "raptor", "", "Blueprint", "/Game/PrimalEarth/Dinos/Dilo/Dilo_Character_BP.Dilo_Character_BP_C");
+        let import = import_sheet(&config, &rows);
+
+        assert!(import.errors.is_empty(), "{:?}", import.errors);
+        assert_eq!(import.diff.changed.len(), 1);
+        let after = import.diff.changed[0].after.as_ref().unwrap();
+        assert_eq!(after["Blueprint"], "Blueprint'/Game/PrimalEarth/Dinos/Dilo/Dilo_Character_BP.Dilo_Character_BP'");
+        // Fields without a column survive
+        assert_eq!(after["Neutered"], true);
+    }
+
+    #[test]
+    fn bad_cells_are_reported_on_their_row_and_column() {
+        let config = config();
+        let cases = [
+            ("metal", "", "Price", "lots", "\"lots\" isn't a number"),
+            ("day", "", "Price", "-5", "Price -5 can't be negative"),
+            ("raptor", "", "Blueprint", "Blueprint'/Game/Foo/Bar.Bar", "The opening quote is never closed"),
+            ("stone", "", "Type", "dino", "SellItems entries must have Type \"item\""),
+            ("metal", "", "Type", "weapon", "Unknown Type \"weapon\""),
+            ("metal", "1", "Line", "first", "\"first\" isn't a line number"),
+            ("metal", "1", "ForceBlueprint", "maybe", "\"maybe\" isn't true or false"),
+        ];
+        for (key, line, column, text, message) in cases {
+            let mut rows = sheet_rows(&config);
+            let row = set_cell(&mut rows, key, line, column, text);
+            let import = import_sheet(&config, &rows);
+
+            assert_eq!(import.errors.len(), 1, "{} {}: {:?}", key, column, import.errors);
+            let error = &import.errors[0];
+            assert_eq!((error.row, error.column.as_deref(), error.key.as_deref()), (row, Some(column), Some(key)));
+            assert!(error.message.starts_with(message), "{}", error.message);
+            // The entry is left as it is rather than half-applied
+            assert!(import.diff.changed.is_empty(), "{:?}", import.diff.changed);
+        }
+    }
+
+    #[test]
+    fn deleted_rows_are_removed_and_new_keys_added() {
+        let config = config();
+        let mut rows: Vec<Vec<String>> = sheet_rows(&config).into_iter().filter(|row| row[1] != "metal" && row[1] != "starter").collect();
+        let mut added = row_for("ShopItems", "wood", None, &json!({ "Type": "item", "Price": 5 }), &ENTRY_COLUMNS);
+        added[COLUMNS.iter().position(|column| *column == "Title").unwrap()] = "Wood".to_string();
+        rows.push(added);
+        let import = import_sheet(&config, &rows);
+
+        assert!(import.errors.is_empty(), "{:?}", import.errors);
+        let removed: Vec<(&str, &str)> = import.diff.removed.iter().map(|change| (change.section.as_str(), change.key.as_str())).collect();
+        assert_eq!(removed, vec![("ShopItems", "metal"), ("Kits", "starter")]);
+        assert_eq!(import.diff.removed[0].before.as_ref(), Some(&config["ShopItems"]["metal"]));
+        assert_eq!(import.diff.added.len(), 1);
+        assert_eq!(import.diff.added[0].after, Some(json!({ "Type": "item", "Title": "Wood", "Price": 5 })));
+
+        let mut applied = config.clone();
+        apply_sheet_diff(&mut applied, &import.diff).unwrap();
+        let keys: Vec<&String> = applied["ShopItems"].as_object().unwrap().keys().collect();
+        assert_eq!(keys, vec!["raptor", "day", "wood"]);
+        assert!(applied["Kits"].as_object().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,591 @@
+// src-tauri/src/workspace.rs
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use chrono::{DateTime, Utc};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+use crate::bundle::{read_archive, write_archive};
+use crate::db::{
+    clear_workspace, config_name_exists, load_command_deny_list, load_config_message_packs, load_configs, load_default_message_pack,
+    load_export_path_map, load_export_path_maps, load_export_path_message_pack, load_export_path_message_packs, load_license_info,
+    load_message_packs, load_permissions_source, load_secret_key_info, load_settings, message_pack_name_exists, save_command_deny_list,
+    save_config, save_license_info, save_message_pack, save_permissions_source, save_settings, set_config_message_pack,
+    set_export_path_map, set_export_path_message_pack, settings_saved, ExportPathMap, LicenseInfo, MessagePack, SavedConfig, SecretKeyInfo, Settings,
+};
+
+pub const WORKSPACE_EXTENSION: &str = "arkshopworkspace";
+const WORKSPACE_FORMAT: u32 = 1;
+const WORKSPACE_FILE: &str = "workspace.json";
+// Custom catalogs from <app data>/catalogs go under this folder in the archive
+const CATALOG_FOLDER: &str = "catalogs/";
+const SCHEDULED_PREFIX: &str = "workspace-";
+
+// Everything in settings.db worth moving to another PC. Saved configs keep their
+// encrypted secrets; secret_key tells the restoring side how they were sealed.
+// There is no separate server profile table: a server profile is a saved config's
+// custom_export_paths together with the message pack and map set for each of those
+// paths, so export_path_message_packs and export_path_maps carry the rest of it.
+// Configs have no revision history to back up.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Workspace {
+    pub format: u32,
+    pub app_version: String,
+    pub created_at: DateTime<Utc>,
+    pub settings: Option<Settings>,
+    pub license: Option<LicenseInfo>,
+    pub saved_configs: Vec<SavedConfig>,
+    pub message_packs: Vec<MessagePack>,
+    // (config id, pack id) using the ids above
+    pub config_message_packs: Vec<(i64, i64)>,
+    pub export_path_message_packs: Vec<(String, i64)>,
+    pub export_path_maps: Vec<ExportPathMap>,
+    pub permissions_source: Option<String>,
+    pub command_deny_list: Option<String>,
+    pub secret_key: Option<SecretKeyInfo>,
+}
+
+pub struct WorkspaceArchive {
+    pub workspace: Workspace,
+    // Catalog file names and contents
+    pub catalogs: Vec<(String, Vec<u8>)>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RestoreMode {
+    // Adds the archive's data next to what's already here, renaming on name clashes
+    Merge,
+    // Wipes the local workspace first
+    Replace,
+}
+
+#[derive(Serialize, Debug)]
+pub struct RestoreConflict {
+    pub kind: String,
+    pub name: String,
+    pub resolution: String,
+}
+
+#[derive(Serialize, Debug, Default)]
+pub struct RestoreReport {
+    pub configs_restored: usize,
+    pub message_packs_restored: usize,
+    pub catalogs_restored: usize,
+    pub conflicts: Vec<RestoreConflict>,
+    // Saved configs (or "Permissions source") whose secrets couldn't be decrypted and were cleared
+    pub unrecoverable_secrets: Vec<String>,
+    // settings.db copy taken before a replace
+    pub backup: Option<String>,
+    // Folder the catalogs a replace removed were moved to
+    pub catalog_backup: Option<String>,
+}
+
+impl RestoreReport {
+    fn conflict(&mut self, kind: &str, name: &str, resolution: String) {
+        self.conflicts.push(RestoreConflict {
+            kind: kind.to_string(),
+            name: name.to_string(),
+            resolution,
+        });
+    }
+}
+
+// "name", then "name (2)", "name (3)"... until exists says it's free
+pub fn unique_name<F>(base: &str, exists: F) -> rusqlite::Result<String>
+where
+    F: Fn(&str) -> rusqlite::Result<bool>,
+{
+    let mut name = base.to_string();
+    let mut counter = 2;
+    while exists(&name)? {
+        name = format!("{} ({})", base, counter);
+        counter += 1;
+    }
+    Ok(name)
+}
+
+pub fn collect_workspace(conn: &Connection, app_version: String) -> rusqlite::Result<Workspace> {
+    let settings = if settings_saved(conn)? { Some(load_settings(conn)?) } else { None };
+    Ok(Workspace {
+        format: WORKSPACE_FORMAT,
+        app_version,
+        created_at: Utc::now(),
+        settings,
+        license: load_license_info(conn)?,
+        saved_configs: load_configs(conn)?,
+        message_packs: load_message_packs(conn)?,
+        config_message_packs: load_config_message_packs(conn)?,
+        export_path_message_packs: load_export_path_message_packs(conn)?,
+        export_path_maps: load_export_path_maps(conn)?,
+        permissions_source: load_permissions_source(conn)?,
+        command_deny_list: load_command_deny_list(conn)?,
+        secret_key: load_secret_key_info(conn)?,
+    })
+}
+
+pub fn write_workspace(path: &Path, workspace: &Workspace, catalog_dir: Option<&Path>) -> Result<(), String> {
+    let mut files = vec![(WORKSPACE_FILE.to_string(), serde_json::to_vec_pretty(workspace).map_err(|e| e.to_string())?)];
+
+    if let Some(entries) = catalog_dir.and_then(|dir| fs::read_dir(dir).ok()) {
+        let mut catalogs: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().map_or(false, |ext| ext == "json"))
+            .collect();
+        catalogs.sort();
+        for catalog in catalogs {
+            let name = catalog.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+            let bytes = fs::read(&catalog).map_err(|e| format!("Failed to read catalog {}: {}", name, e))?;
+            files.push((format!("{}{}", CATALOG_FOLDER, name), bytes));
+        }
+    }
+
+    write_archive(path, &files)
+}
+
+pub fn read_workspace(path: &Path) -> Result<WorkspaceArchive, String> {
+    let files = read_archive(path)?;
+    let raw = files.get(WORKSPACE_FILE).ok_or("Archive has no workspace.json")?;
+    let workspace: Workspace = serde_json::from_slice(raw).map_err(|e| format!("Failed to parse {}: {}", WORKSPACE_FILE, e))?;
+    if workspace.format > WORKSPACE_FORMAT {
+        return Err(format!("Workspace format {} is newer than this app supports, please update", workspace.format));
+    }
+
+    let catalogs = files
+        .into_iter()
+        .filter_map(|(name, bytes)| name.strip_prefix(CATALOG_FOLDER).map(|name| (name.to_string(), bytes)))
+        // Only plain file names, nothing that could escape the catalogs folder
+        .filter(|(name, _)| Path::new(name).file_name().map_or(false, |file| file == name.as_str()))
+        .collect();
+    Ok(WorkspaceArchive { workspace, catalogs })
+}
+
+// Single-row settings keep the local value in merge mode when one exists
+fn restore_single<T: PartialEq>(report: &mut RestoreReport, kind: &str, local: Option<T>, restored: &Option<T>) -> bool {
+    match (local, restored) {
+        (_, None) => false,
+        (None, Some(_)) => true,
+        (Some(local), Some(restored)) => {
+            if &local != restored {
+                report.conflict(kind, kind, "kept the existing value".to_string());
+            }
+            false
+        }
+    }
+}
+
+// Secrets in workspace are expected to be sealed with this machine's key already
+pub fn restore_workspace(conn: &Connection, workspace: &Workspace, mode: RestoreMode, report: &mut RestoreReport) -> rusqlite::Result<()> {
+    if mode == RestoreMode::Replace {
+        clear_workspace(conn)?;
+    }
+
+    if let Some(settings) = &workspace.settings {
+        if settings_saved(conn)? {
+            report.conflict("settings", "settings", "kept the existing settings".to_string());
+        } else {
+            save_settings(conn, settings)?;
+        }
+    }
+    if let Some(license) = &workspace.license {
+        match load_license_info(conn)? {
+            Some(local) if local.license_key != license.license_key => {
+                report.conflict("license", "license", "kept the existing license".to_string());
+            }
+            Some(_) => {}
+            None => save_license_info(conn, license)?,
+        }
+    }
+    if restore_single(report, "permissions source", load_permissions_source(conn)?, &workspace.permissions_source) {
+        save_permissions_source(conn, workspace.permissions_source.as_deref())?;
+    }
+    if restore_single(report, "command deny list", load_command_deny_list(conn)?, &workspace.command_deny_list) {
+        save_command_deny_list(conn, workspace.command_deny_list.as_deref())?;
+    }
+
+    let has_default_pack = load_default_message_pack(conn)?.is_some();
+    let mut pack_ids: HashMap<i64, i64> = HashMap::new();
+    for pack in &workspace.message_packs {
+        let name = unique_name(&pack.name, |name| message_pack_name_exists(conn, name))?;
+        if name != pack.name {
+            report.conflict("message pack", &pack.name, format!("restored as \"{}\"", name));
+        }
+        let id = save_message_pack(conn, &MessagePack {
+            id: None,
+            name,
+            messages: pack.messages.clone(),
+            is_default: pack.is_default && !has_default_pack,
+        })?;
+        if let Some(old_id) = pack.id {
+            pack_ids.insert(old_id, id);
+        }
+        report.message_packs_restored += 1;
+    }
+
+    let mut config_ids: HashMap<i64, i64> = HashMap::new();
+    for saved in &workspace.saved_configs {
+        let name = unique_name(&saved.name, |name| config_name_exists(conn, name))?;
+        if name != saved.name {
+            report.conflict("config", &saved.name, format!("restored as \"{}\"", name));
+        }
+        let id = save_config(conn, &SavedConfig {
+            id: None,
+            name,
+            config: saved.config.clone(),
+            custom_export_paths: saved.custom_export_paths.clone(),
+        })?;
+        if let Some(old_id) = saved.id {
+            config_ids.insert(old_id, id);
+        }
+        report.configs_restored += 1;
+    }
+
+    for (config_id, pack_id) in &workspace.config_message_packs {
+        if let (Some(config_id), Some(pack_id)) = (config_ids.get(config_id), pack_ids.get(pack_id)) {
+            set_config_message_pack(conn, *config_id, Some(*pack_id))?;
+        }
+    }
+    for (path, pack_id) in &workspace.export_path_message_packs {
+        if load_export_path_message_pack(conn, path)?.is_some() {
+            report.conflict("export path message pack", path, "kept the existing message pack".to_string());
+        } else if let Some(pack_id) = pack_ids.get(pack_id) {
+            set_export_path_message_pack(conn, path, Some(*pack_id))?;
+        }
+    }
+    for path_map in &workspace.export_path_maps {
+        match load_export_path_map(conn, &path_map.path)? {
+            Some(local) if &local != path_map => report.conflict("export path map", &path_map.path, format!("kept {}", local.map)),
+            Some(_) => {}
+            None => set_export_path_map(conn, &path_map.path, Some(&path_map.map), path_map.drop_incompatible)?,
+        }
+    }
+
+    Ok(())
+}
+
+// Catalogs written next to the catalogs folder, waiting to be moved into it.
+// Dropping it without installing leaves the catalogs folder as it was.
+pub struct StagedCatalogs {
+    dir: PathBuf,
+    staging: PathBuf,
+    names: Vec<String>,
+    mode: RestoreMode,
+}
+
+// Merge keeps a local catalog that differs from the archived one; replace swaps the
+// whole set of *.json catalogs for the archived ones
+pub fn stage_catalogs(dir: &Path, catalogs: &[(String, Vec<u8>)], mode: RestoreMode, report: &mut RestoreReport) -> std::io::Result<StagedCatalogs> {
+    let staging = dir.with_file_name(format!("catalogs.restore-{}", std::process::id()));
+    if staging.exists() {
+        fs::remove_dir_all(&staging)?;
+    }
+    let mut staged = StagedCatalogs { dir: dir.to_path_buf(), staging, names: Vec::new(), mode };
+    for (name, bytes) in catalogs {
+        if mode == RestoreMode::Merge {
+            if let Ok(local) = fs::read(dir.join(name)) {
+                if &local != bytes {
+                    report.conflict("catalog", name, "kept the existing file".to_string());
+                }
+                continue;
+            }
+        }
+        fs::create_dir_all(&staged.staging)?;
+        fs::write(staged.staging.join(name), bytes)?;
+        staged.names.push(name.clone());
+        report.catalogs_restored += 1;
+    }
+    Ok(staged)
+}
+
+fn json_files(dir: &Path) -> std::io::Result<Vec<PathBuf>> {
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file() && path.extension().map_or(false, |ext| ext == "json") {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+impl StagedCatalogs {
+    // Moves the staged catalogs into place. On replace the existing *.json catalogs are moved
+    // to catalogs.<timestamp>.bak first, the way settings.db is backed up, and that folder is
+    // returned. If a move fails the backed up catalogs are put back.
+    pub fn install(self) -> std::io::Result<Option<PathBuf>> {
+        let existing = match self.mode {
+            RestoreMode::Replace => json_files(&self.dir)?,
+            RestoreMode::Merge => Vec::new(),
+        };
+        if self.names.is_empty() && existing.is_empty() {
+            return Ok(None);
+        }
+        fs::create_dir_all(&self.dir)?;
+
+        let backup = if existing.is_empty() {
+            None
+        } else {
+            let backup = self.dir.with_file_name(format!("catalogs.{}.bak", Utc::now().format("%Y%m%d%H%M%S")));
+            fs::create_dir_all(&backup)?;
+            for path in &existing {
+                fs::copy(path, backup.join(path.file_name().unwrap_or_default()))?;
+            }
+            Some(backup)
+        };
+
+        if let Err(e) = self.move_into_place(&existing) {
+            if let Some(backup) = &backup {
+                for path in &existing {
+                    let _ = fs::copy(backup.join(path.file_name().unwrap_or_default()), path);
+                }
+            }
+            return Err(e);
+        }
+        Ok(backup)
+    }
+
+    fn move_into_place(&self, existing: &[PathBuf]) -> std::io::Result<()> {
+        for path in existing {
+            fs::remove_file(path)?;
+        }
+        for name in &self.names {
+            fs::rename(self.staging.join(name), self.dir.join(name))?;
+        }
+        Ok(())
+    }
+}
+
+impl Drop for StagedCatalogs {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.staging);
+    }
+}
+
+pub fn scheduled_backup_name(time: DateTime<Utc>) -> String {
+    format!("{}{}.{}", SCHEDULED_PREFIX, time.format("%Y%m%d-%H%M%S"), WORKSPACE_EXTENSION)
+}
+
+// Deletes the oldest scheduled backups in dir beyond retention; other files are left alone
+pub fn prune_backups(dir: &Path, retention: usize) -> std::io::Result<Vec<PathBuf>> {
+    let suffix = format!(".{}", WORKSPACE_EXTENSION);
+    let mut backups: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .map_or(false, |name| name.starts_with(SCHEDULED_PREFIX) && name.ends_with(&suffix))
+        })
+        .collect();
+    // Names carry the timestamp, so they sort oldest first
+    backups.sort();
+
+    let excess = backups.len().saturating_sub(retention);
+    let removed: Vec<PathBuf> = backups.into_iter().take(excess).collect();
+    for path in &removed {
+        fs::remove_file(path)?;
+    }
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::migrations::migrate;
+
+    fn database() -> Connection {
+        let mut conn = Connection::open_in_memory().unwrap();
+        // A fresh database has nothing to back up, so the path is never used
+        migrate(&mut conn, Path::new("unused.db")).unwrap();
+        conn
+    }
+
+    fn seed(conn: &Connection, map: &str) {
+        save_settings(conn, &Settings {
+            output_path: "C:\\ArkShop".to_string(),
+            auto_save_enabled: false,
+            auto_save_interval: 5,
+            show_tooltips: true,
+        }).unwrap();
+        save_config(conn, &SavedConfig {
+            id: None,
+            name: "main".to_string(),
+            config: "{}".to_string(),
+            custom_export_paths: Some(vec!["C:\\server".to_string()]),
+        }).unwrap();
+        save_message_pack(conn, &MessagePack {
+            id: None,
+            name: "Default".to_string(),
+            messages: "{}".to_string(),
+            is_default: true,
+        }).unwrap();
+        set_export_path_map(conn, "C:\\server", Some(map), true).unwrap();
+    }
+
+    fn config_names(conn: &Connection) -> Vec<String> {
+        load_configs(conn).unwrap().into_iter().map(|saved| saved.name).collect()
+    }
+
+    #[test]
+    fn merge_renames_clashes_and_keeps_local_values() {
+        let source = database();
+        seed(&source, "Ragnarok");
+        set_export_path_map(&source, "C:\\other", Some("Fjordur"), false).unwrap();
+        let workspace = collect_workspace(&source, "1.0.0".to_string()).unwrap();
+
+        let local = database();
+        seed(&local, "TheIsland");
+        let mut report = RestoreReport::default();
+        restore_workspace(&local, &workspace, RestoreMode::Merge, &mut report).unwrap();
+
+        assert_eq!(report.configs_restored, 1);
+        assert_eq!(report.message_packs_restored, 1);
+        let conflicts: Vec<(&str, &str, &str)> = report
+            .conflicts
+            .iter()
+            .map(|conflict| (conflict.kind.as_str(), conflict.name.as_str(), conflict.resolution.as_str()))
+            .collect();
+        assert_eq!(conflicts, vec![
+            ("settings", "settings", "kept the existing settings"),
+            ("message pack", "Default", "restored as \"Default (2)\""),
+            ("config", "main", "restored as \"main (2)\""),
+            ("export path map", "C:\\server", "kept TheIsland"),
+        ]);
+        assert_eq!(config_names(&local), vec!["main", "main (2)"]);
+        assert_eq!(load_export_path_map(&local, "C:\\server").unwrap().unwrap().map, "TheIsland");
+        assert_eq!(load_export_path_map(&local, "C:\\other").unwrap().unwrap().map, "Fjordur");
+        // Only one pack can stay the default
+        assert_eq!(load_message_packs(&local).unwrap().iter().filter(|pack| pack.is_default).count(), 1);
+    }
+
+    #[test]
+    fn replace_takes_the_archive_without_conflicts() {
+        let source = database();
+        seed(&source, "Ragnarok");
+        let workspace = collect_workspace(&source, "1.0.0".to_string()).unwrap();
+
+        let local = database();
+        seed(&local, "TheIsland");
+        save_config(&local, &SavedConfig {
+            id: None,
+            name: "local only".to_string(),
+            config: "{}".to_string(),
+            custom_export_paths: None,
+        }).unwrap();
+        let mut report = RestoreReport::default();
+        restore_workspace(&local, &workspace, RestoreMode::Replace, &mut report).unwrap();
+
+        assert!(report.conflicts.is_empty(), "{:?}", report.conflicts);
+        assert_eq!(config_names(&local), vec!["main"]);
+        let path_map = load_export_path_map(&local, "C:\\server").unwrap().unwrap();
+        assert_eq!(path_map.map, "Ragnarok");
+        assert!(path_map.drop_incompatible);
+    }
+
+    #[test]
+    fn unique_name_counts_up_from_two() {
+        let taken = ["main", "main (2)"];
+        assert_eq!(unique_name("other", |name| Ok(taken.contains(&name))).unwrap(), "other");
+        assert_eq!(unique_name("main", |name| Ok(taken.contains(&name))).unwrap(), "main (3)");
+    }
+
+    #[test]
+    fn prune_backups_removes_the_oldest_scheduled_backups() {
+        let dir = std::env::temp_dir().join(format!("arkshop-prune-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let times = ["2024-03-01T00:00:00Z", "2023-12-31T23:59:59Z", "2024-01-15T12:00:00Z", "2024-02-01T08:30:00Z"];
+        for time in times {
+            fs::write(dir.join(scheduled_backup_name(time.parse().unwrap())), b"").unwrap();
+        }
+        // Not made by the schedule, so never pruned
+        fs::write(dir.join("manual.arkshopworkspace"), b"").unwrap();
+        fs::write(dir.join("workspace-notes.txt"), b"").unwrap();
+
+        let removed = prune_backups(&dir, 2).unwrap();
+
+        let names = |paths: Vec<PathBuf>| -> Vec<String> {
+            paths.iter().map(|path| path.file_name().unwrap().to_string_lossy().into_owned()).collect()
+        };
+        assert_eq!(names(removed), vec!["workspace-20231231-235959.arkshopworkspace", "workspace-20240115-120000.arkshopworkspace"]);
+        let mut left = names(fs::read_dir(&dir).unwrap().map(|entry| entry.unwrap().path()).collect());
+        left.sort();
+        assert_eq!(left, vec![
+            "manual.arkshopworkspace",
+            "workspace-20240201-083000.arkshopworkspace",
+            "workspace-20240301-000000.arkshopworkspace",
+            "workspace-notes.txt",
+        ]);
+        assert!(prune_backups(&dir, 2).unwrap().is_empty());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    fn file_names(dir: &Path) -> Vec<String> {
+        let mut names: Vec<String> = fs::read_dir(dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    }
+
+    // A root of its own per test, holding the catalogs folder and whatever lands beside it
+    fn catalog_root(test: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("arkshop-{}-{}", test, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("catalogs")).unwrap();
+        fs::write(root.join("catalogs").join("local.json"), b"local").unwrap();
+        fs::write(root.join("catalogs").join("shared.json"), b"old").unwrap();
+        fs::write(root.join("catalogs").join("notes.txt"), b"notes").unwrap();
+        root
+    }
+
+    fn archived() -> Vec<(String, Vec<u8>)> {
+        vec![("shared.json".to_string(), b"new".to_vec()), ("archived.json".to_string(), b"archived".to_vec())]
+    }
+
+    #[test]
+    fn replace_swaps_the_catalogs_and_backs_up_the_old_ones() {
+        let root = catalog_root("replace-catalogs");
+        let dir = root.join("catalogs");
+        let mut report = RestoreReport::default();
+
+        let staged = stage_catalogs(&dir, &archived(), RestoreMode::Replace, &mut report).unwrap();
+        // Nothing moves until the database has committed
+        assert_eq!(file_names(&dir), vec!["local.json", "notes.txt", "shared.json"]);
+        let backup = staged.install().unwrap().unwrap();
+
+        assert_eq!(report.catalogs_restored, 2);
+        assert_eq!(file_names(&dir), vec!["archived.json", "notes.txt", "shared.json"]);
+        assert_eq!(fs::read(dir.join("shared.json")).unwrap(), b"new");
+        assert_eq!(file_names(&backup), vec!["local.json", "shared.json"]);
+        assert_eq!(fs::read(backup.join("shared.json")).unwrap(), b"old");
+        // Only the catalogs folder and its backup are left, no staging folder
+        assert_eq!(file_names(&root).len(), 2);
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn merge_keeps_local_catalogs_and_dropping_the_stage_changes_nothing() {
+        let root = catalog_root("merge-catalogs");
+        let dir = root.join("catalogs");
+        let mut report = RestoreReport::default();
+
+        // As when the database transaction fails
+        drop(stage_catalogs(&dir, &archived(), RestoreMode::Merge, &mut report).unwrap());
+        assert_eq!(file_names(&dir), vec!["local.json", "notes.txt", "shared.json"]);
+        assert_eq!(file_names(&root), vec!["catalogs"]);
+
+        let mut report = RestoreReport::default();
+        let staged = stage_catalogs(&dir, &archived(), RestoreMode::Merge, &mut report).unwrap();
+        assert_eq!(staged.install().unwrap(), None);
+        assert_eq!(report.catalogs_restored, 1);
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(file_names(&dir), vec!["archived.json", "local.json", "notes.txt", "shared.json"]);
+        assert_eq!(fs::read(dir.join("shared.json")).unwrap(), b"old");
+        fs::remove_dir_all(&root).unwrap();
+    }
+}